@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{LevelCompleted, RestartLevel};
+use crate::scores::{platform_storage, SaveStorage};
+use crate::toast::ShowToast;
+use crate::{GameEvent, Quality};
+
+const ACHIEVEMENTS_NAME: &str = "achievements";
+/// Handovers in a row without dropping the baton needed for [`Achievement::Streak`].
+const STREAK_HANDOVERS: u32 = 10;
+/// Level time to beat for [`Achievement::SpeedRun`].
+const SPEED_RUN_TIME: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Achievement {
+    /// A handover with the hands as close as they get.
+    Perfect,
+    /// Ten handovers in a row without a drop.
+    Streak,
+    /// Finishing a level in under thirty seconds.
+    SpeedRun,
+}
+
+impl Achievement {
+    pub fn title(self) -> &'static str {
+        match self {
+            Achievement::Perfect => "PERFECT HANDOVER",
+            Achievement::Streak => "STEADY HANDS",
+            Achievement::SpeedRun => "IN A HURRY",
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct AchievementUnlocked(pub Achievement);
+
+/// Achievements unlocked so far, across sessions.
+#[derive(Resource)]
+pub struct Achievements {
+    unlocked: HashSet<Achievement>,
+    storage: Box<dyn SaveStorage>,
+}
+
+impl Default for Achievements {
+    fn default() -> Self {
+        let storage = platform_storage(ACHIEVEMENTS_NAME);
+        let unlocked = storage
+            .load()
+            .and_then(|contents| match ron::from_str(&contents) {
+                Ok(unlocked) => Some(unlocked),
+                Err(error) => {
+                    warn!("Could not read achievements: {error}");
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self { unlocked, storage }
+    }
+}
+
+impl Achievements {
+    /// Unlocks and saves `achievement`, returning whether it was newly unlocked.
+    fn unlock(&mut self, achievement: Achievement) -> bool {
+        if !self.unlocked.insert(achievement) {
+            return false;
+        }
+
+        match ron::to_string(&self.unlocked) {
+            Ok(contents) => self.storage.save(&contents),
+            Err(error) => warn!("Could not serialize achievements: {error}"),
+        }
+
+        true
+    }
+}
+
+fn unlock(
+    achievements: &mut Achievements,
+    unlocked: &mut EventWriter<AchievementUnlocked>,
+    achievement: Achievement,
+) {
+    if achievements.unlock(achievement) {
+        unlocked.send(AchievementUnlocked(achievement));
+    }
+}
+
+fn system_check_handovers(
+    mut streak: Local<u32>,
+    mut events: EventReader<GameEvent>,
+    mut restarts: EventReader<RestartLevel>,
+    mut achievements: ResMut<Achievements>,
+    mut unlocked: EventWriter<AchievementUnlocked>,
+) {
+    // A restart starts the count over, just like a drop.
+    if restarts.read().count() > 0 {
+        *streak = 0;
+    }
+
+    for event in events.read() {
+        match event {
+            GameEvent::HandOver { quality } => {
+                *streak += 1;
+                if *quality == Quality::Perfect {
+                    unlock(&mut achievements, &mut unlocked, Achievement::Perfect);
+                }
+                if *streak >= STREAK_HANDOVERS {
+                    unlock(&mut achievements, &mut unlocked, Achievement::Streak);
+                }
+            }
+            // Losing the baton to a hazard breaks the streak as much as dropping it.
+            GameEvent::Drop | GameEvent::HazardReset => *streak = 0,
+            _ => {}
+        }
+    }
+}
+
+fn system_check_level_time(
+    mut completed: EventReader<LevelCompleted>,
+    mut achievements: ResMut<Achievements>,
+    mut unlocked: EventWriter<AchievementUnlocked>,
+) {
+    for LevelCompleted { time, .. } in completed.read() {
+        if *time < SPEED_RUN_TIME {
+            unlock(&mut achievements, &mut unlocked, Achievement::SpeedRun);
+        }
+    }
+}
+
+fn system_toast_unlocks(
+    mut unlocked: EventReader<AchievementUnlocked>,
+    mut toasts: EventWriter<ShowToast>,
+) {
+    for AchievementUnlocked(achievement) in unlocked.read() {
+        toasts.send(ShowToast(format!("UNLOCKED {}", achievement.title())));
+    }
+}
+
+pub struct AchievementsBundle;
+
+impl Plugin for AchievementsBundle {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Achievements>()
+            .add_event::<AchievementUnlocked>()
+            .add_systems(Update, system_check_handovers)
+            .add_systems(Update, system_check_level_time)
+            .add_systems(Update, system_toast_unlocks);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoStorage;
+
+    impl SaveStorage for NoStorage {
+        fn load(&self) -> Option<String> {
+            None
+        }
+
+        fn save(&self, _contents: &str) {}
+    }
+
+    fn hand_overs(app: &mut App, count: u32) {
+        for _ in 0..count {
+            app.world_mut().send_event(GameEvent::HandOver {
+                quality: Quality::Good,
+            });
+        }
+        app.update();
+    }
+
+    #[test]
+    fn a_hazard_reset_breaks_the_streak() {
+        let mut app = App::new();
+        app.insert_resource(Achievements {
+            unlocked: HashSet::new(),
+            storage: Box::new(NoStorage),
+        })
+        .add_event::<GameEvent>()
+        .add_event::<RestartLevel>()
+        .add_event::<AchievementUnlocked>()
+        .add_systems(Update, system_check_handovers);
+
+        hand_overs(&mut app, STREAK_HANDOVERS - 1);
+        app.world_mut().send_event(GameEvent::HazardReset);
+        hand_overs(&mut app, 1);
+        let unlocked = &app.world().resource::<Achievements>().unlocked;
+        assert!(!unlocked.contains(&Achievement::Streak));
+
+        hand_overs(&mut app, STREAK_HANDOVERS - 1);
+        let unlocked = &app.world().resource::<Achievements>().unlocked;
+        assert!(unlocked.contains(&Achievement::Streak));
+    }
+}
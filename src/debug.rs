@@ -1,12 +1,20 @@
-use bevy::color::palettes::tailwind::GREEN_600;
+use bevy::color::palettes::tailwind::{GREEN_600, ORANGE_400, PINK_400, SKY_400};
+use bevy::core::FrameCount;
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::ecs::entity::Entities;
+use bevy::input::mouse::MouseWheel;
+use bevy::math::bounding::BoundingVolume;
 use bevy::math::vec2;
 use bevy::prelude::*;
 
-use crate::game::Collision;
+use crate::game::{
+    Active, AppState, ColliderScale, Collision, Direction, Game, GameEvent, GameSet, Hand,
+    LevelCompleted, Overlap, Progress, Speed, MINIMAP_MARGIN, MINIMAP_SIZE,
+};
 use crate::input::{Action, ActionInput};
 
 fn debug_gizmo_grid(mut show: Local<bool>, action_input: Res<ActionInput>, mut gizmos: Gizmos) {
-    if action_input.just_pressed(Action::DebugShowCollisions) {
+    if action_input.just_pressed(Action::DebugShowGrid) {
         *show = !*show;
     }
 
@@ -27,7 +35,7 @@ fn debug_show_collision_gizmos(
     mut show: Local<bool>,
     action_input: Res<ActionInput>,
     mut gizmos: Gizmos,
-    query: Query<(&GlobalTransform, &Collision)>,
+    query: Query<(&GlobalTransform, &Collision, Option<&ColliderScale>)>,
 ) {
     if action_input.just_pressed(Action::DebugShowCollisions) {
         *show = !*show;
@@ -36,21 +44,365 @@ fn debug_show_collision_gizmos(
     if !*show {
         return;
     }
-    for (transform, collision) in query.iter() {
-        let translation = transform.translation().xy();
-        match collision {
-            Collision::Circle(circle) => {
-                gizmos.primitive_2d(circle, translation, 0., GREEN_600);
-            }
+    for (transform, collision, scale) in query.iter() {
+        let bounds = collision.bounding_circle(transform.translation().xy(), scale);
+        gizmos.circle_2d(bounds.center(), bounds.radius(), GREEN_600);
+    }
+}
+
+/// Length of the arrow drawn in each hand's direction of travel.
+const VELOCITY_ARROW_LENGTH: f32 = 96.;
+
+fn debug_show_velocity_gizmos(
+    mut show: Local<bool>,
+    action_input: Res<ActionInput>,
+    mut gizmos: Gizmos,
+    query: Query<(&GlobalTransform, &Progress, &Direction), With<Hand>>,
+) {
+    if action_input.just_pressed(Action::DebugShowCollisions) {
+        *show = !*show;
+    }
+
+    if !*show {
+        return;
+    }
+
+    for (transform, progress, Direction(direction)) in query.iter() {
+        // Progress runs counterclockwise, so the tangent is the radius rotated a quarter turn.
+        let angle = progress.angle();
+        let tangent = Vec2::new(-angle.sin(), angle.cos()) * *direction;
+        let start = transform.translation().xy();
+        gizmos.arrow_2d(start, start + tangent * VELOCITY_ARROW_LENGTH, ORANGE_400);
+    }
+}
+
+fn debug_show_overlap_gizmos(
+    mut show: Local<bool>,
+    action_input: Res<ActionInput>,
+    mut gizmos: Gizmos,
+    overlap: Res<Overlap>,
+    transforms: Query<&GlobalTransform>,
+    active: Query<(), With<Active>>,
+) {
+    if action_input.just_pressed(Action::DebugShowOverlaps) {
+        *show = !*show;
+    }
+
+    if !*show {
+        return;
+    }
+
+    // Pairs are stored in both orders, only draw each once.
+    for (e1, e2) in overlap.pairs().iter().filter(|(e1, e2)| e1 < e2) {
+        let (Ok(t1), Ok(t2)) = (transforms.get(*e1), transforms.get(*e2)) else {
+            continue;
+        };
+
+        let color = if active.contains(*e1) || active.contains(*e2) {
+            PINK_400
+        } else {
+            SKY_400
+        };
+        gizmos.line_2d(t1.translation().xy(), t2.translation().xy(), color);
+    }
+}
+
+fn debug_log_game_events(
+    mut show: Local<bool>,
+    action_input: Res<ActionInput>,
+    frame: Res<FrameCount>,
+    time: Res<Time>,
+    mut events: EventReader<GameEvent>,
+    mut completed: EventReader<LevelCompleted>,
+    active: Query<(Entity, &Progress), (With<Hand>, With<Active>)>,
+) {
+    if action_input.just_pressed(Action::DebugLogEvents) {
+        *show = !*show;
+    }
+
+    if !*show {
+        events.clear();
+        completed.clear();
+        return;
+    }
+
+    // The grab's commands have been applied by now, so after a handover this is the hand
+    // that received the baton.
+    let hand = match active.get_single() {
+        Ok((entity, Progress(progress))) => format!("{entity} at {progress:.3}"),
+        Err(_) => "-".to_string(),
+    };
+
+    for event in events.read() {
+        info!(
+            "frame {} {:.3}s {event:?}, active {hand}",
+            frame.0,
+            time.elapsed_seconds()
+        );
+    }
+
+    for LevelCompleted {
+        level,
+        time: run_time,
+    } in completed.read()
+    {
+        info!(
+            "frame {} {:.3}s level {level} completed in {:.3}s",
+            frame.0,
+            time.elapsed_seconds(),
+            run_time.as_secs_f32()
+        );
+    }
+}
+
+const TIME_SCALE_MIN: f32 = 0.125;
+const TIME_SCALE_MAX: f32 = 4.;
+
+/// Relative speed of virtual time, halved and doubled by the debug actions.
+#[derive(Resource)]
+struct DebugTimeScale(f32);
+
+impl Default for DebugTimeScale {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+fn debug_time_scale(
+    action_input: Res<ActionInput>,
+    mut scale: ResMut<DebugTimeScale>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if action_input.just_pressed(Action::DebugSlower) {
+        scale.0 = (scale.0 / 2.).max(TIME_SCALE_MIN);
+    }
+
+    if action_input.just_pressed(Action::DebugFaster) {
+        scale.0 = (scale.0 * 2.).min(TIME_SCALE_MAX);
+    }
+
+    if scale.is_changed() {
+        time.set_relative_speed(scale.0);
+    }
+}
+
+/// World units the free camera pans per second, at the default zoom.
+const FREE_CAM_SPEED: f32 = 640.;
+/// Zoom change per scroll wheel notch.
+const FREE_CAM_ZOOM_STEP: f32 = 0.1;
+const FREE_CAM_ZOOM_MIN: f32 = 0.25;
+const FREE_CAM_ZOOM_MAX: f32 = 8.;
+
+/// When on, the cameras stop following play and are moved with WASD and the scroll wheel.
+#[derive(Resource, Default, PartialEq)]
+struct DebugFreeCam(bool);
+
+fn debug_toggle_free_cam(action_input: Res<ActionInput>, mut free_cam: ResMut<DebugFreeCam>) {
+    if action_input.just_pressed(Action::DebugFreeCam) {
+        free_cam.0 = !free_cam.0;
+    }
+}
+
+fn debug_move_free_cam(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut scroll: EventReader<MouseWheel>,
+    time: Res<Time<Real>>,
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<Camera>>,
+) {
+    let direction = [
+        (KeyCode::KeyW, Vec2::Y),
+        (KeyCode::KeyA, Vec2::NEG_X),
+        (KeyCode::KeyS, Vec2::NEG_Y),
+        (KeyCode::KeyD, Vec2::X),
+    ]
+    .into_iter()
+    .filter(|(key, _)| keys.pressed(*key))
+    .map(|(_, direction)| direction)
+    .sum::<Vec2>()
+    .normalize_or_zero();
+    let notches: f32 = scroll.read().map(|event| event.y.signum()).sum();
+
+    // Every layer's camera gets the same movement, so they stay lined up.
+    for (mut transform, mut projection) in &mut cameras {
+        let pan = direction * FREE_CAM_SPEED * projection.scale * time.delta_seconds();
+        transform.translation += pan.extend(0.);
+        projection.scale = (projection.scale * (1. + FREE_CAM_ZOOM_STEP).powf(-notches))
+            .clamp(FREE_CAM_ZOOM_MIN, FREE_CAM_ZOOM_MAX);
+    }
+}
+
+/// Holds virtual time still, and with it everything that runs on it, so the
+/// simulation can be advanced one fixed step at a time.
+#[derive(Resource, Default)]
+struct DebugPaused(bool);
+
+/// Set to let a paused simulation run a single fixed step.
+#[derive(Resource, Default)]
+struct DebugStep(bool);
+
+fn debug_pause(
+    action_input: Res<ActionInput>,
+    mut paused: ResMut<DebugPaused>,
+    mut step: ResMut<DebugStep>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if action_input.just_pressed(Action::DebugPause) {
+        paused.0 = !paused.0;
+        if paused.0 {
+            time.pause();
+        } else {
+            time.unpause();
         }
     }
+
+    // Time runs again until a fixed step has gone by, then stops once more.
+    if paused.0 && action_input.just_pressed(Action::DebugStep) {
+        step.0 = true;
+        time.unpause();
+    }
+}
+
+fn debug_simulation_running(paused: Res<DebugPaused>, step: Res<DebugStep>) -> bool {
+    !paused.0 || step.0
+}
+
+/// Run conditions can't write, so the step is used up once the gated sets have run.
+/// Any further steps the same frame would have run are skipped by the run condition.
+fn debug_consume_step(
+    mut step: ResMut<DebugStep>,
+    paused: Res<DebugPaused>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if !step.0 {
+        return;
+    }
+
+    step.0 = false;
+    if paused.0 {
+        time.pause();
+    }
+}
+
+#[derive(Component)]
+struct DebugHudText;
+
+fn debug_setup_hud(mut commands: Commands) {
+    commands.spawn((
+        DebugHudText,
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: 16.,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                // Stacked above the minimap, which has the corner itself.
+                bottom: Val::Px(MINIMAP_MARGIN + MINIMAP_SIZE.y + 8.),
+                left: Val::Px(MINIMAP_MARGIN),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+    ));
+}
+
+fn debug_update_hud(
+    action_input: Res<ActionInput>,
+    diagnostics: Res<DiagnosticsStore>,
+    entities: &Entities,
+    app_state: Res<State<AppState>>,
+    state: Option<Res<State<Game>>>,
+    overlap: Res<Overlap>,
+    scale: Res<DebugTimeScale>,
+    paused: Res<DebugPaused>,
+    active: Query<(&Progress, &Speed, &Direction), (With<Hand>, With<Active>)>,
+    mut query: Query<(&mut Text, &mut Visibility), With<DebugHudText>>,
+) {
+    let Ok((mut text, mut visibility)) = query.get_single_mut() else {
+        return;
+    };
+
+    if action_input.just_pressed(Action::DebugShowHud) {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+    }
+
+    if *visibility == Visibility::Hidden {
+        return;
+    }
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or(0.);
+    let hand = match active.get_single() {
+        Ok((Progress(progress), Speed(speed), Direction(direction))) => {
+            format!("{progress:.3} @ {:.2}", speed * direction)
+        }
+        Err(_) => "-".to_string(),
+    };
+
+    let state = match state {
+        Some(state) => format!("{:?}", state.get()),
+        None => format!("{:?}", app_state.get()),
+    };
+
+    text.sections[0].value = format!(
+        "fps {fps:.0}\nentities {}\nstate {state}\nactive {hand}\noverlaps {}\ntime x{}{}",
+        entities.len(),
+        overlap.pairs().len() / 2,
+        scale.0,
+        if paused.0 { " (paused)" } else { "" },
+    );
 }
 
 pub struct DebugBundle;
 
 impl Plugin for DebugBundle {
     fn build(&self, app: &mut App) {
-        app.add_systems(PostUpdate, debug_show_collision_gizmos)
-            .add_systems(PostUpdate, debug_gizmo_grid);
+        app.add_plugins(FrameTimeDiagnosticsPlugin)
+            .init_resource::<DebugTimeScale>()
+            .add_systems(Update, debug_time_scale)
+            .init_resource::<DebugPaused>()
+            .init_resource::<DebugStep>()
+            .add_systems(Update, debug_pause)
+            .configure_sets(
+                FixedUpdate,
+                (
+                    GameSet::Input,
+                    GameSet::Movement,
+                    GameSet::Collision,
+                    GameSet::Grab,
+                )
+                    .run_if(debug_simulation_running),
+            )
+            .add_systems(FixedUpdate, debug_consume_step.after(GameSet::Grab))
+            .init_resource::<DebugFreeCam>()
+            .configure_sets(
+                Update,
+                GameSet::Camera.run_if(not(resource_equals(DebugFreeCam(true)))),
+            )
+            .add_systems(
+                Update,
+                (
+                    debug_toggle_free_cam,
+                    debug_move_free_cam.run_if(resource_equals(DebugFreeCam(true))),
+                )
+                    .chain(),
+            )
+            .add_systems(Startup, debug_setup_hud)
+            .add_systems(Update, debug_update_hud)
+            .add_systems(PostUpdate, debug_show_collision_gizmos)
+            .add_systems(PostUpdate, debug_gizmo_grid)
+            .add_systems(PostUpdate, debug_show_velocity_gizmos)
+            .add_systems(PostUpdate, debug_show_overlap_gizmos)
+            .add_systems(Update, debug_log_game_events);
     }
 }
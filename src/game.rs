@@ -4,57 +4,162 @@
 // Feel free to delete this line.
 #![allow(clippy::too_many_arguments, clippy::type_complexity)]
 
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::f32::consts::PI;
 use std::time::Duration;
 
+use bevy::asset::{LoadState, UntypedAssetId};
+use bevy::audio::Volume as AudioVolume;
 use bevy::color::palettes::css::GRAY;
 use bevy::color::palettes::tailwind::{
-    BLUE_100, GREEN_100, ORANGE_100, PINK_100, PURPLE_100, RED_100, TEAL_100, YELLOW_100,
+    AMBER_300, BLUE_100, GREEN_100, ORANGE_100, PINK_100, PURPLE_100, RED_100, TEAL_100, YELLOW_100,
 };
-use bevy::math::bounding::{BoundingCircle, IntersectsVolume};
+use bevy::ecs::system::{EntityCommands, SystemParam};
+use bevy::input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::math::bounding::{BoundingCircle, BoundingVolume, IntersectsVolume};
 use bevy::math::vec2;
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
-use input::{Action, ActionInput};
+use bevy::time::Stopwatch;
+use input::{key_label, Action, ActionInput, KeyBindings, StepInput};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::input;
+use crate::launch::LaunchOptions;
+use crate::scores::{platform_storage, HighScores, SaveStorage};
 
 const LAYER_ACTIVE: usize = 1;
 const LAYER_INACTIVE: usize = 0;
 const TINT_ACTIVE: Color = Color::WHITE;
 const TINT_INACTIVE: Color = Color::Srgba(GRAY);
+const TINT_HAZARD: Color = Color::Srgba(RED_100);
+const TINT_GHOST: Color = Color::srgba(0.6, 0.8, 1., 0.35);
+const TINT_PICKUP: Color = Color::Srgba(AMBER_300);
 const SPACING_CYCLE: f32 = 64.;
+const BATON_SIZE: f32 = 128.;
 const RADIUS_CYCLE: f32 = 192.;
+/// World distance between neighbouring cycles, level positions are given in these units.
+const CYCLE_UNIT: f32 = RADIUS_CYCLE * 2. + SPACING_CYCLE;
 
-#[derive(States, Debug, Clone, PartialEq, Eq, Hash)]
-enum Game {
+/// Top level screens. [`Game`] only exists while a run is in progress.
+#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AppState {
+    #[default]
+    Menu,
+    /// The settings menu opened from the main menu.
+    Settings,
+    InGame,
+}
+
+#[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[source(AppState = AppState::InGame)]
+pub enum Game {
+    #[default]
+    Loading,
+    /// A practice level that walks first-time players through the controls.
+    Tutorial,
+    Countdown,
     Playing,
+    Paused,
+    Settings,
+    LevelComplete,
     Finished,
+    Failed,
 }
 
-#[derive(Component, Clone)]
-struct Holding(Option<Entity>);
+/// What a hand's grip is doing. Stable.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "inspector", derive(Reflect), reflect(Component))]
+pub enum Grip {
+    /// Open, ready to grab.
+    #[default]
+    Empty,
+    /// Closed on nothing after a grab that missed, until the next press opens it.
+    Reaching,
+    /// Closed around an item.
+    Holding(Entity),
+}
 
+/// How far around its cycle a hand is, counterclockwise from the x axis, in `0..1` turns.
 #[derive(Component, Clone)]
-struct Progress(f32);
+#[cfg_attr(feature = "inspector", derive(Reflect), reflect(Component))]
+pub struct Progress(pub f32);
+
+impl Progress {
+    /// Moves by `delta` turns in either direction, wrapping back into `0..1`.
+    pub fn advance(&mut self, delta: f32) {
+        self.0 = (self.0 + delta).rem_euclid(1.);
+    }
+
+    pub fn angle(&self) -> f32 {
+        self.0 * 2. * PI
+    }
+}
+
+/// Progress at the start of the last fixed step, used to interpolate hand visuals.
+#[derive(Component)]
+struct PreviousProgress(f32);
+
+/// Where a collider is as of the last fixed step. Collision and grabs are judged by this
+/// instead of the transforms, which are interpolated between steps for drawing.
+#[derive(Component, Clone, Copy, Default)]
+struct SimPosition(Vec2);
 
+/// Distance from a cycle's center to its hands.
 #[derive(Component)]
-struct Radius(f32);
+#[cfg_attr(feature = "inspector", derive(Reflect), reflect(Component))]
+pub struct Radius(pub f32);
 
+/// How fast a hand travels around its cycle, always positive.
 #[derive(Component, Clone)]
-struct Speed(f32);
+#[cfg_attr(feature = "inspector", derive(Reflect), reflect(Component))]
+pub struct Speed(pub f32);
+
+/// Which way a hand travels around its cycle, `1.` counterclockwise and `-1.` clockwise.
+#[derive(Component, Clone, Copy)]
+pub struct Direction(pub f32);
+
+impl Direction {
+    fn reversed(self) -> Self {
+        Self(-self.0)
+    }
+}
 
+/// The hand that currently carries the baton. Stable.
 #[derive(Component)]
-struct Active;
+pub struct Active;
 
+/// The baton. Stable.
 #[derive(Component)]
-struct Item;
+pub struct Item;
 
+/// A ring that hands travel around. Stable.
 #[derive(Component, Clone)]
-struct Cycle;
+pub struct Cycle;
 
+/// A cycle's own color, as an index into the color scheme's palette picked by the order
+/// cycles are placed in.
+#[derive(Component, Clone, Copy)]
+struct CycleColor(usize);
+
+/// How much the active cycle brightens its color, and the others darken theirs.
+const CYCLE_ACTIVE_LIGHTEN: f32 = 0.1;
+const CYCLE_INACTIVE_DARKEN: f32 = 0.35;
+
+#[derive(Component)]
+struct CycleMotion {
+    from: Vec2,
+    to: Vec2,
+    period: f32,
+    /// Seconds moved for since the level spawned, so every run starts in the same phase.
+    elapsed: f32,
+}
+
+/// A hand travelling around a cycle. Stable.
 #[derive(Component, Clone)]
-struct Hand;
+pub struct Hand;
 
 #[derive(Resource, Default)]
 struct CameraFocus(Vec2);
@@ -65,12 +170,54 @@ struct AfterImage;
 #[derive(Component)]
 struct FadeOutSpeed(f32);
 
-#[derive(Event)]
-enum GameEvent {
+/// Something that happened to the baton during a run. Stable, so plugins can react to play.
+#[derive(Event, Debug)]
+pub enum GameEvent {
     Drop,
     Grab,
     GrabEmpty,
-    HandOver,
+    HandOver {
+        quality: Quality,
+    },
+    /// The baton touched a hazard and went back to the previous hand.
+    HazardReset,
+    /// The baton collected a pickup worth `value` points at `position`.
+    Pickup {
+        value: u32,
+        position: Vec2,
+    },
+}
+
+/// Sent once when the baton reaches a level's finish, before the state changes. Stable.
+#[derive(Event)]
+pub struct LevelCompleted {
+    pub level: usize,
+    /// Run time at the moment the finish was reached.
+    pub time: Duration,
+}
+
+/// Hand distances at or below which a handover counts as that quality.
+const QUALITY_PERFECT_DISTANCE: f32 = 32.;
+const QUALITY_GOOD_DISTANCE: f32 = 80.;
+
+/// How close the hands were on a handover. Stable.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Quality {
+    Perfect,
+    Good,
+    Ok,
+}
+
+impl Quality {
+    fn from_distance(distance: f32) -> Self {
+        if distance <= QUALITY_PERFECT_DISTANCE {
+            Quality::Perfect
+        } else if distance <= QUALITY_GOOD_DISTANCE {
+            Quality::Good
+        } else {
+            Quality::Ok
+        }
+    }
 }
 
 #[derive(Component, Clone)]
@@ -78,12 +225,43 @@ pub enum Collision {
     Circle(Circle),
 }
 
+/// Multiplies the collider's base size, so it can grow along with its sprite.
+#[derive(Component, Clone, Copy)]
+pub struct ColliderScale(pub f32);
+
+impl Collision {
+    /// The collider as a circle around `center` in world space, with any [`ColliderScale`] applied.
+    pub fn bounding_circle(&self, center: Vec2, scale: Option<&ColliderScale>) -> BoundingCircle {
+        let scale = scale.map_or(1., |scale| scale.0);
+        match self {
+            Collision::Circle(circle) => BoundingCircle::new(center, circle.radius * scale),
+        }
+    }
+}
+
+/// Where the baton has to be carried to. Stable.
 #[derive(Component, Clone)]
-struct Finish;
+pub struct Finish;
 
 #[derive(Component)]
 struct CanHold;
 
+/// Sends the baton back to the previous hand when it is carried into it.
+#[derive(Component)]
+struct Hazard;
+
+const PICKUP_SIZE: f32 = 48.;
+
+/// Bonus points collected by carrying the baton through it.
+#[derive(Component)]
+struct Pickup {
+    value: u32,
+}
+
+/// Points from pickups collected during the current run.
+#[derive(Resource, Default)]
+struct Score(u32);
+
 #[derive(Bundle)]
 struct CycleBundle {
     sprite_bundle: SpriteBundle,
@@ -117,24 +295,94 @@ impl CycleBundle {
     }
 }
 
+/// Which set of textures a hand is drawn with.
+#[derive(Clone, Copy, Default)]
+enum HandSkin {
+    #[default]
+    Plain,
+    /// Closes into a thumbs up instead of a fist.
+    Thumb,
+}
+
+/// Where each hand image ends up in the hand atlas, in the order they are added to it.
+const HAND_FRAME_OPEN: usize = 0;
+const HAND_FRAME_CLOSED: usize = 1;
+const HAND_FRAME_CLOSED_THUMB: usize = 2;
+/// Pixels left between the hand images in the atlas, so filtering doesn't bleed neighbours in.
+const HAND_ATLAS_PADDING: u32 = 2;
+
+/// The hand images packed into one texture, built once they have loaded.
+#[derive(Clone, Default)]
+struct HandAtlas {
+    image: Handle<Image>,
+    layout: Handle<TextureAtlasLayout>,
+}
+
+/// Animation frames a hand steps through as it grabs the baton, from open to closed,
+/// as indices into the hand atlas.
+#[derive(Component, Clone)]
+struct HandSprites {
+    atlas: HandAtlas,
+    frames: Vec<usize>,
+}
+
+impl HandSprites {
+    fn open(&self) -> usize {
+        self.frames[0]
+    }
+
+    fn closed(&self) -> usize {
+        self.frames[self.frames.len() - 1]
+    }
+
+    fn texture_atlas(&self, index: usize) -> TextureAtlas {
+        TextureAtlas {
+            layout: self.atlas.layout.clone(),
+            index,
+        }
+    }
+}
+
+/// Seconds each frame of a hand animation stays on screen.
+const HAND_FRAME_DURATION: f32 = 0.05;
+
+#[derive(Component, Deref, DerefMut)]
+struct AnimationTimer(Timer);
+
+/// The frame a hand is showing and whether it is animating toward closed or open.
+#[derive(Component, Default)]
+struct HandAnimation {
+    frame: usize,
+    closing: bool,
+}
+
 #[derive(Bundle)]
 struct HandBundle {
     hand: Hand,
+    hand_sprites: HandSprites,
+    texture_atlas: TextureAtlas,
+    animation: HandAnimation,
+    animation_timer: AnimationTimer,
     progress: Progress,
+    previous_progress: PreviousProgress,
     collision: Collision,
+    position: SimPosition,
     sprite: SpriteBundle,
     render_layers: RenderLayers,
     can_hold: CanHold,
+    grip: Grip,
 }
 
 impl HandBundle {
-    fn new(texture: &Handle<Image>) -> Self {
+    fn new(hand_sprites: HandSprites) -> Self {
         Self {
             hand: Hand,
             progress: Progress(0.5),
+            previous_progress: PreviousProgress(0.5),
             collision: Collision::Circle(Circle::new(64.)),
+            position: SimPosition::default(),
             sprite: SpriteBundle {
-                texture: texture.clone(),
+                texture: hand_sprites.atlas.image.clone(),
                 sprite: Sprite {
                     custom_size: Some(Vec2::splat(64.0)),
                     ..default()
@@ -142,10 +390,24 @@ impl HandBundle {
                 transform: Transform::from_translation(Vec3::new(0., 0., 2.)),
                 ..default()
             },
+            texture_atlas: hand_sprites.texture_atlas(hand_sprites.open()),
+            hand_sprites,
+            animation: HandAnimation::default(),
+            animation_timer: AnimationTimer(Timer::from_seconds(
+                HAND_FRAME_DURATION,
+                TimerMode::Repeating,
+            )),
             can_hold: CanHold,
+            grip: Grip::Empty,
             render_layers: RenderLayers::layer(LAYER_INACTIVE),
         }
     }
+
+    fn progress(mut self, progress: f32) -> Self {
+        self.progress = Progress(progress);
+        self.previous_progress = PreviousProgress(progress);
+        self
+    }
 }
 
 fn system_setup_camera(mut commands: Commands) {
@@ -172,547 +434,3960 @@ fn system_setup_camera(mut commands: Commands) {
     ));
 }
 
-fn system_setup_entities(mut commands: Commands, asset_server: ResMut<AssetServer>) {
-    let hand_open_image = asset_server.load::<Image>("hand-open.png");
-    let cycle_image = asset_server.load::<Image>("cycle.png");
-    let finish_image = asset_server.load::<Image>("finish.png");
-    let baton_image = asset_server.load("statue.png");
-
-    enum Place {
-        Cycle(Vec2, f32),
-        CycleStart(Vec2, f32),
-        Baton(Vec2),
-        Finish(Vec2),
-    }
-
-    let cycles = [
-        Place::Baton(vec2(-0.5, 0.)),
-        Place::CycleStart(vec2(0., 0.), 0.5),
-        Place::Cycle(vec2(1., 0.), 1.),
-        Place::Cycle(vec2(2., 0.), 1.5),
-        Place::Cycle(vec2(3., 0.), 2.),
-        // Place::Cycle(vec2(4., 0.), 2.5),
-        // Place::Cycle(vec2(5., 0.), 3.),
-        // Place::Cycle(vec2(6., 0.), 3.5),
-        // Place::Cycle(vec2(7., 0.), 4.),
-        // Place::Cycle(vec2(8., 0.), 4.5),
-        // Place::Cycle(vec2(9., 0.), 5.),
-        Place::Finish(vec2(3.5, 0.)),
-    ];
-
-    for place in &cycles {
-        let conversion = RADIUS_CYCLE * 2. + SPACING_CYCLE;
-        match place {
-            Place::Cycle(position, speed) | Place::CycleStart(position, speed) => {
-                commands
-                    .spawn(CycleBundle::new(&cycle_image).translation(*position * conversion))
-                    .with_children(|parent| {
-                        let mut hand = parent.spawn(HandBundle::new(&hand_open_image));
-                        hand.insert(Speed(*speed));
-                        if let Place::CycleStart(_, _) = place {
-                            hand.insert(Active);
-                        }
-                    });
-            }
-            Place::Finish(position) => {
-                commands.spawn((
-                    Finish,
-                    Collision::Circle(Circle::new(64.)),
-                    Speed(0.),
-                    SpriteBundle {
-                        texture: finish_image.clone(),
-                        sprite: Sprite {
-                            custom_size: Some(Vec2::splat(64.0)),
-                            ..default()
-                        },
-                        transform: Transform::from_translation(position.extend(1.) * conversion),
-                        ..default()
-                    },
-                    CanHold,
-                    RenderLayers::layer(LAYER_INACTIVE),
-                ));
-            }
-            Place::Baton(position) => {
-                commands.spawn((
-                    Item,
-                    Collision::Circle(Circle::new(40.)),
-                    SpriteBundle {
-                        texture: baton_image.clone(),
-                        sprite: Sprite {
-                            custom_size: Some(Vec2::new(128.0, 128.0)),
-                            ..default()
-                        },
-                        transform: Transform::from_translation(position.extend(1.) * conversion),
-                        ..default()
-                    },
-                    RenderLayers::layer(LAYER_INACTIVE),
-                ));
-            }
-        };
+/// Colors the background shifts between, from the start of a level to its finish.
+#[derive(Resource)]
+pub struct BackgroundGradient {
+    pub start: Color,
+    pub end: Color,
+}
+
+impl Default for BackgroundGradient {
+    fn default() -> Self {
+        Self {
+            start: Color::srgb_u8(43, 44, 47),
+            end: Color::srgb(0.2, 0.12, 0.3),
+        }
     }
 }
 
-const CYCLE_COLOR: [Color; 8] = [
-    Color::Srgba(GREEN_100),
-    Color::Srgba(RED_100),
-    Color::Srgba(PURPLE_100),
-    Color::Srgba(YELLOW_100),
-    Color::Srgba(BLUE_100),
-    Color::Srgba(TEAL_100),
-    Color::Srgba(ORANGE_100),
-    Color::Srgba(PINK_100),
-];
+/// A sprite covering the view behind everything else.
+#[derive(Component)]
+struct Background;
 
-fn system_after_images(
-    mut index: Local<usize>,
-    mut timer: Local<Timer>,
-    time: Res<Time>,
-    query: Query<(&GlobalTransform, &Speed, Ref<Holding>), With<Active>>,
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
+/// How much bigger than the view the background is, so zooming out never shows its edges.
+const BACKGROUND_OVERSCAN: f32 = 1.5;
+
+fn system_setup_background(mut commands: Commands, gradient: Res<BackgroundGradient>) {
+    commands.spawn((
+        Background,
+        SpriteBundle {
+            sprite: Sprite {
+                color: gradient.start,
+                ..default()
+            },
+            transform: Transform::from_xyz(0., 0., -100.),
+            ..default()
+        },
+        RenderLayers::layer(LAYER_INACTIVE),
+    ));
+}
+
+fn system_update_background(
+    gradient: Res<BackgroundGradient>,
+    camera: Query<(&GlobalTransform, &OrthographicProjection), With<Camera>>,
+    active: Query<&GlobalTransform, (With<Hand>, With<Active>)>,
+    finish: Query<&GlobalTransform, With<Finish>>,
+    cycles: Query<&GlobalTransform, With<Cycle>>,
+    mut background: Query<(&mut Sprite, &mut Transform), With<Background>>,
 ) {
-    let Ok((global_transform, Speed(speed), holding)) = query.get_single() else {
+    let Ok((mut sprite, mut transform)) = background.get_single_mut() else {
         return;
     };
 
-    let hand_closed = asset_server.load::<Image>("hand-closed.png");
-    if holding.0.is_none() {
-        return;
+    if let Some((camera, projection)) = camera.iter().next() {
+        transform.translation = camera.translation().xy().extend(transform.translation.z);
+        sprite.custom_size = Some(projection.area.size() * BACKGROUND_OVERSCAN);
     }
 
-    if holding.is_added() && holding.0.is_some() {
-        timer.set_duration(Duration::from_millis((10. / speed.abs()) as u64));
-        timer.reset();
+    // How far along the level the active hand is, measured from the cycle farthest from the finish.
+    let progress = match (active.get_single(), finish.get_single()) {
+        (Ok(hand), Ok(finish)) => {
+            let finish = finish.translation().xy();
+            let total = cycles
+                .iter()
+                .map(|cycle| cycle.translation().xy().distance(finish))
+                .fold(0., f32::max);
+            let remaining = hand.translation().xy().distance(finish);
+            if total > 0. {
+                (1. - remaining / total).clamp(0., 1.)
+            } else {
+                0.
+            }
+        }
+        _ => 0.,
     };
 
-    timer.tick(time.delta());
-
-    if timer.finished() {
-        let mut color = CYCLE_COLOR[*index];
-        color.set_alpha(0.7);
+    let color = gradient.start.mix(&gradient.end, progress);
+    if sprite.color != color {
+        sprite.color = color;
+    }
+}
 
-        commands.spawn((
-            AfterImage,
-            FadeOutSpeed(3. * speed.abs()),
-            SpriteBundle {
-                texture: hand_closed.clone(),
-                sprite: Sprite {
-                    custom_size: Some(Vec2::splat(64.0)),
-                    color,
-                    ..default()
-                },
-                transform: Transform::from_translation(global_transform.translation()),
-                ..default()
-            },
-        ));
+#[derive(Clone, Copy)]
+enum Place {
+    /// A cycle with its speed and how many hands are evenly spaced around it.
+    Cycle(Vec2, f32, u8),
+    CycleStart(Vec2, f32),
+    /// A cycle drifting back and forth between two positions over a period in seconds.
+    CycleMoving(Vec2, Vec2, f32, f32),
+    Baton(Vec2),
+    Finish(Vec2),
+    /// A hazard with its radius in world units.
+    Hazard(Vec2, f32),
+    /// A pickup with the points it is worth.
+    Pickup(Vec2, u32),
+}
 
-        *index = (*index + 1) % CYCLE_COLOR.len();
-        timer.reset();
-    }
+#[derive(Default)]
+struct LevelDef {
+    places: Vec<Place>,
+    /// Cycles whose hands use other textures, by their order among the level's cycles.
+    skins: Vec<(usize, HandSkin)>,
+    /// Where the level's cycles start in the color order, so generated pieces carry it on.
+    first_color: usize,
+    /// How heavy the level's baton is, 0 for the standard one.
+    baton_weight: f32,
 }
 
-fn fade_out_after_images(
-    mut commands: Commands,
-    time: Res<Time>,
+const PROCEDURAL_SPEED_MIN: f32 = 0.5;
+const PROCEDURAL_SPEED_MAX: f32 = 3.;
+/// How far a generated cycle may sit above or below its neighbours, in cycle units.
+const PROCEDURAL_DRIFT: f32 = 0.25;
 
-    mut query: Query<(Entity, &FadeOutSpeed, &mut Sprite), With<AfterImage>>,
-) {
-    for (entity, FadeOutSpeed(speed), mut sprite) in &mut query {
-        let next_alpha = sprite.color.alpha() - speed * time.delta_seconds();
-        if let Some(next_size) = sprite.custom_size {
-            sprite.custom_size = Some(next_size - Vec2::splat(64. * speed * time.delta_seconds()));
+impl LevelDef {
+    fn tutorial() -> Self {
+        Self {
+            places: vec![
+                Place::Baton(vec2(-0.5, 0.)),
+                Place::CycleStart(vec2(0., 0.), 0.5),
+                Place::Cycle(vec2(1., 0.), 0.75, 1),
+                Place::Cycle(vec2(2., 0.), 1., 1),
+            ],
+            ..default()
         }
-        sprite.color.set_alpha(next_alpha);
-        if sprite.color.alpha() <= 0. {
-            commands.entity(entity).despawn();
+    }
+
+    /// The opening of an endless run, the rest is generated as the baton moves along.
+    fn endless_start() -> Self {
+        Self {
+            places: vec![
+                Place::Baton(vec2(-0.5, 0.)),
+                Place::CycleStart(vec2(0., 0.), PROCEDURAL_SPEED_MIN),
+            ],
+            ..default()
         }
     }
 }
 
-fn system_progress(
-    mut query: Query<(&mut Progress, &Speed, Option<&Holding>), With<Active>>,
-    time: Res<Time>,
-) {
-    for (mut progress, Speed(speed), holding) in query.iter_mut() {
-        progress.0 += match holding {
-            Some(Holding(Some(_))) => time.delta_seconds() * speed,
-            _ => time.delta_seconds() * 0.5 * speed.signum(), // slower speed to pick up baton again
-        };
+impl Place {
+    fn procedural_cycle(rng: &mut impl Rng, index: usize) -> Self {
+        let position = vec2(
+            index as f32,
+            rng.gen_range(-PROCEDURAL_DRIFT..=PROCEDURAL_DRIFT),
+        );
+        let speed = rng.gen_range(PROCEDURAL_SPEED_MIN..=PROCEDURAL_SPEED_MAX);
+        Place::Cycle(position, speed, 1)
+    }
+}
+
+/// Seed for procedurally generated levels, the same seed always builds the same level.
+#[derive(Resource, Clone, Copy, Default)]
+pub struct LevelSeed(pub u64);
+
+#[derive(Resource)]
+struct GameRng(ChaCha8Rng);
+
+impl FromWorld for GameRng {
+    fn from_world(world: &mut World) -> Self {
+        let seed = world
+            .get_resource::<LevelSeed>()
+            .copied()
+            .unwrap_or_default();
+        Self(ChaCha8Rng::seed_from_u64(seed.0))
+    }
+}
+
+fn system_reseed_rng(seed: Res<LevelSeed>, mut rng: ResMut<GameRng>) {
+    if seed.is_changed() {
+        rng.0 = ChaCha8Rng::seed_from_u64(seed.0);
+    }
+}
 
-        if progress.0 > 1. {
-            progress.0 = progress.0 - progress.0.trunc();
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Campaign,
+    /// Cycles keep being generated ahead of the baton and there is no finish.
+    Endless,
+}
+
+impl GameMode {
+    /// The mode called `name`, as in `--mode endless`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "campaign" => Some(GameMode::Campaign),
+            "endless" => Some(GameMode::Endless),
+            _ => None,
         }
     }
 }
 
+/// Cycles kept generated ahead of the active hand in endless mode.
+const ENDLESS_LOOKAHEAD: usize = 4;
+/// Cycles this far behind the active hand are despawned in endless mode.
+const ENDLESS_BEHIND: f32 = 3.;
+
+/// Index of the next cycle to generate in endless mode.
 #[derive(Resource, Default)]
-struct Overlap {
-    overlaps: Vec<(Entity, Entity)>,
+struct EndlessFrontier(usize);
+
+/// Furthest distance reached in endless mode, in cycles.
+#[derive(Resource, Default)]
+struct EndlessDistance(f32);
+
+/// Cycle speed multiplier for each difficulty.
+const DIFFICULTY_EASY_SPEED: f32 = 0.7;
+const DIFFICULTY_NORMAL_SPEED: f32 = 1.;
+const DIFFICULTY_HARD_SPEED: f32 = 1.4;
+
+/// How fast the cycles turn, applied to each level as it spawns.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
 }
 
-impl Overlap {
-    fn update(&mut self, overlaps: Vec<(Entity, Entity)>) {
-        self.overlaps = overlaps;
+impl Difficulty {
+    /// The difficulty called `name`, as in `--difficulty hard`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
     }
 
-    fn with(&self, entity: Entity) -> Vec<Entity> {
-        self.overlaps
-            .iter()
-            .filter_map(|(e1, e2)| if *e1 == entity { Some(*e2) } else { None })
-            .collect()
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "EASY",
+            Difficulty::Normal => "NORMAL",
+            Difficulty::Hard => "HARD",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    fn speed_multiplier(self) -> f32 {
+        match self {
+            Difficulty::Easy => DIFFICULTY_EASY_SPEED,
+            Difficulty::Normal => DIFFICULTY_NORMAL_SPEED,
+            Difficulty::Hard => DIFFICULTY_HARD_SPEED,
+        }
+    }
+
+    /// Seconds a dropped baton may lie around, longer the slower the cycles turn.
+    fn drop_timeout(self) -> f32 {
+        DROP_TIMEOUT / self.speed_multiplier()
     }
 }
 
-fn system_check_overlap(
-    query: Query<(Entity, &GlobalTransform, &Collision)>,
-    mut current_overlap: ResMut<Overlap>,
-) {
-    let mut overlaps = vec![];
+/// The [`Difficulty`] the current level was spawned with. [`Difficulty`] can be changed from
+/// the settings mid-run, so scores and ghosts are filed under this instead.
+#[derive(Resource, Default, Clone, Copy)]
+pub struct RunDifficulty(pub Difficulty);
 
-    for [(e1, t1, c1), (e2, t2, c2)] in query.iter_combinations() {
-        match (c1, c2) {
-            (Collision::Circle(c1), Collision::Circle(c2)) => {
-                let bc1 = BoundingCircle::new(t1.translation().xy(), c1.radius);
-                let bc2 = BoundingCircle::new(t2.translation().xy(), c2.radius);
+#[derive(SystemParam)]
+struct LevelSource<'w> {
+    mode: Res<'w, GameMode>,
+    levels: Res<'w, Levels>,
+    current_level: ResMut<'w, CurrentLevel>,
+    seed: Res<'w, LevelSeed>,
+    rng: ResMut<'w, GameRng>,
+    frontier: ResMut<'w, EndlessFrontier>,
+    distance: ResMut<'w, EndlessDistance>,
+    difficulty: Res<'w, Difficulty>,
+    run_difficulty: ResMut<'w, RunDifficulty>,
+}
 
-                if bc1.intersects(&bc2) {
-                    overlaps.push((e1, e2));
-                    overlaps.push((e2, e1));
+impl LevelSource<'_> {
+    fn spawn(&mut self, commands: &mut Commands, assets: &GameAssets) {
+        self.run_difficulty.0 = *self.difficulty;
+        match *self.mode {
+            GameMode::Campaign => {
+                spawn_level(
+                    commands,
+                    assets,
+                    &self.levels.0[self.current_level.0],
+                    self.run_difficulty.0,
+                );
+            }
+            GameMode::Endless => {
+                self.rng.0 = ChaCha8Rng::seed_from_u64(self.seed.0);
+                self.frontier.0 = 1;
+                self.distance.0 = 0.;
+                spawn_level(
+                    commands,
+                    assets,
+                    &LevelDef::endless_start(),
+                    self.run_difficulty.0,
+                );
+                for _ in 0..ENDLESS_LOOKAHEAD {
+                    self.extend(commands, assets);
                 }
             }
         }
     }
 
-    current_overlap.update(overlaps)
+    fn extend(&mut self, commands: &mut Commands, assets: &GameAssets) {
+        let cycle = Place::procedural_cycle(&mut self.rng.0, self.frontier.0);
+        spawn_level(
+            commands,
+            assets,
+            &LevelDef {
+                places: vec![cycle],
+                first_color: self.frontier.0,
+                ..default()
+            },
+            self.run_difficulty.0,
+        );
+        self.frontier.0 += 1;
+    }
 }
 
-fn system_grab_toggle(
+fn system_extend_endless(
     mut commands: Commands,
-    overlap: Res<Overlap>,
-    active: Query<(Entity, &Speed, Option<&Holding>), (With<CanHold>, With<Active>)>,
-    hand_overs: Query<(Entity, Option<&Speed>), (With<CanHold>, Without<Active>)>,
-    mut items: Query<(Entity, &mut Transform), With<Item>>,
-    action_input: Res<ActionInput>,
-    mut event_writer: EventWriter<GameEvent>,
+    assets: Res<GameAssets>,
+    mut source: LevelSource,
+    active: Query<&GlobalTransform, With<Active>>,
+    cycles: Query<(Entity, &GlobalTransform), (With<Cycle>, With<LevelEntity>)>,
 ) {
-    if !action_input.just_pressed(Action::Grab) {
+    if *source.mode != GameMode::Endless {
         return;
     }
 
-    let Ok((entity, Speed(speed), maybe_holding)) = active.get_single() else {
+    let Ok(active) = active.get_single() else {
         return;
     };
 
-    match maybe_holding {
-        Some(Holding(Some(item))) => {
-            let overlaps = overlap.with(entity);
-            let is_overlapping = overlaps.into_iter().find_map(|e| hand_overs.get(e).ok());
-
-            if let Some((other, maybe_speed_other)) = is_overlapping {
-                let mut newly_active = commands.entity(other);
-                newly_active.insert(Holding(Some(*item)));
-                newly_active.insert(Active);
-                if let Some(Speed(speed_other)) = maybe_speed_other {
-                    newly_active.insert(Speed(speed_other.abs() * -speed.signum()));
-                }
+    let reached = active.translation().x / CYCLE_UNIT;
+    source.distance.0 = source.distance.0.max(reached);
 
-                commands.entity(*item).set_parent_in_place(other);
+    while (source.frontier.0 as f32) - reached < ENDLESS_LOOKAHEAD as f32 {
+        source.extend(&mut commands, &assets);
+    }
 
-                let mut old_active = commands.entity(entity);
-                old_active.remove::<Active>();
-                old_active.remove::<Holding>();
-                event_writer.send(GameEvent::HandOver);
-            } else {
-                commands.entity(*item).remove_parent_in_place();
-                commands.entity(entity).remove::<Holding>();
-                event_writer.send(GameEvent::Drop);
-            }
-        }
-        Some(_) => {
-            commands.entity(entity).remove::<Holding>();
-        }
-        _ => {
-            if let Some(item) = overlap
-                .with(entity)
-                .into_iter()
-                .find(|e| items.get_mut(*e).is_ok())
-            {
-                commands.entity(item).set_parent_in_place(entity);
-                commands.entity(entity).insert(Holding(Some(item)));
-                event_writer.send(GameEvent::Grab);
-            } else {
-                commands.entity(entity).insert(Holding(None));
-                event_writer.send(GameEvent::GrabEmpty);
-            }
+    for (entity, transform) in &cycles {
+        if transform.translation().x / CYCLE_UNIT < reached - ENDLESS_BEHIND {
+            commands.entity(entity).despawn_recursive();
         }
     }
 }
 
-fn system_play_sfx(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut events: EventReader<GameEvent>,
-) {
-    let hand_over = asset_server.load("hand-over.wav");
-    let select = asset_server.load("select.wav");
-    let select_miss = asset_server.load("select-miss.wav");
+/// Actions listed in the control prompt, with the label shown next to their key.
+const CONTROL_PROMPTS: [(Action, &str); 2] = [(Action::Grab, "GRAB"), (Action::Pause, "PAUSE")];
 
-    for event in events.read() {
-        match event {
-            GameEvent::Drop => {
-                commands.spawn(AudioBundle {
-                    source: select_miss.clone(),
+#[derive(Component)]
+struct ControlPromptText;
+
+fn system_setup_control_prompt(mut commands: Commands) {
+    commands
+        .spawn((
+            StateScoped(AppState::InGame),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(16.),
+                    right: Val::Px(16.),
                     ..default()
-                });
-            }
-            GameEvent::HandOver => {
-                commands.spawn(AudioBundle {
-                    source: hand_over.clone(),
-                    ..default()
-                });
-            }
-            GameEvent::Grab => {
-                commands.spawn(AudioBundle {
-                    source: select.clone(),
-                    ..default()
-                });
-            }
-            GameEvent::GrabEmpty => {
-                commands.spawn(AudioBundle {
-                    source: select_miss.clone(),
-                    ..default()
-                });
-            }
-        }
-    }
+                },
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ControlPromptText,
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 24.,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+            ));
+        });
 }
 
-fn system_clean_up_sfx(mut commands: Commands, sfxs: Query<(Entity, &AudioSink)>) {
-    for (entity, sink) in &sfxs {
-        if sink.is_paused() {
-            commands.entity(entity).despawn();
+fn system_show_control_prompt(
+    bindings: Res<KeyBindings>,
+    mut query: Query<(&mut Text, Ref<ControlPromptText>)>,
+) {
+    for (mut text, marker) in &mut query {
+        if !bindings.is_changed() && !marker.is_added() {
+            continue;
         }
+
+        let prompt = CONTROL_PROMPTS
+            .iter()
+            .filter_map(|(action, label)| {
+                let key = bindings.key(*action)?;
+                Some(format!("[{}] {label}", key_label(key)))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        text.sections[0].value = prompt;
     }
 }
 
-fn system_lerp_item_to_holding(query: Query<&Holding>, mut items: Query<(&mut Transform, &Item)>) {
-    let Ok(Holding(Some(item))) = query.get_single() else {
-        return;
-    };
-
-    let Ok((mut transform, _)) = items.get_mut(*item) else {
-        return;
-    };
+#[derive(Component)]
+struct DistanceText;
 
-    transform.translation = transform
-        .translation
-        .lerp(Vec2::ZERO.extend(transform.translation.z), 0.03);
+fn system_setup_distance_text(mut commands: Commands) {
+    commands.spawn((
+        DistanceText,
+        StateScoped(AppState::InGame),
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: 32.,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(56.),
+                right: Val::Px(16.),
+                ..default()
+            },
+            ..default()
+        },
+    ));
 }
 
-fn system_set_render_layer(
-    mut query: Query<(Entity, Option<&Parent>, &mut RenderLayers), With<Active>>,
-    mut others: Query<
-        &mut RenderLayers,
-        (
-            Or<(With<Hand>, With<Item>, With<Cycle>, With<Finish>)>,
-            Without<Active>,
-        ),
-    >,
-    overlap: Res<Overlap>,
+fn system_show_distance(
+    mode: Res<GameMode>,
+    distance: Res<EndlessDistance>,
+    mut query: Query<&mut Text, With<DistanceText>>,
 ) {
-    for mut render_layers in &mut others {
-        *render_layers = RenderLayers::layer(LAYER_INACTIVE);
+    if !mode.is_changed() && !distance.is_changed() {
+        return;
     }
 
-    if let Ok((entity, parent, mut render_layers)) = query.get_single_mut() {
-        *render_layers = RenderLayers::layer(LAYER_ACTIVE);
-
-        for other in overlap.with(entity) {
-            if let Ok(mut render_layers) = others.get_mut(other) {
-                *render_layers = RenderLayers::layer(LAYER_ACTIVE);
-            }
-        }
-
-        if let Some(mut render_layers) = parent.and_then(|p| others.get_mut(p.get()).ok()) {
-            *render_layers = RenderLayers::layer(LAYER_ACTIVE);
-        }
-    };
+    for mut text in &mut query {
+        text.sections[0].value = match *mode {
+            GameMode::Endless => format!("DISTANCE {:.0}", distance.0),
+            GameMode::Campaign => String::new(),
+        };
+    }
 }
 
-fn system_tint_layers(mut query: Query<(&mut Sprite, &RenderLayers)>) {
-    for (mut sprite, render_layers) in &mut query {
-        if render_layers == &RenderLayers::layer(LAYER_ACTIVE) && sprite.color != TINT_ACTIVE {
-            sprite.color = TINT_ACTIVE;
-        } else if render_layers == &RenderLayers::layer(LAYER_INACTIVE)
-            && sprite.color != TINT_INACTIVE
-        {
-            sprite.color = TINT_INACTIVE;
-        }
+#[derive(Resource)]
+pub struct Levels(Vec<LevelDef>);
+
+impl Levels {
+    pub fn count(&self) -> usize {
+        self.0.len()
     }
 }
 
-fn system_cycle_hand(
-    mut query: Query<(&Children, &Radius), With<Cycle>>,
-    mut hands: Query<(&mut Transform, &Progress), With<Hand>>,
-) {
-    for (children, radius) in query.iter_mut() {
-        for child in children.iter() {
-            let Ok((mut hand, Progress(progress))) = hands.get_mut(*child) else {
-                continue;
-            };
-
-            let angle = progress * 2. * PI;
-            let offset = Vec2::new(angle.cos(), angle.sin()) * radius.0;
-            hand.translation.x = offset.x;
-            hand.translation.y = offset.y;
-        }
+impl Default for Levels {
+    fn default() -> Self {
+        Self(vec![
+            LevelDef {
+                places: vec![
+                    Place::Baton(vec2(-0.5, 0.)),
+                    Place::CycleStart(vec2(0., 0.), 0.5),
+                    Place::Cycle(vec2(1., 0.), 1., 1),
+                    Place::Cycle(vec2(2., 0.), 1.5, 1),
+                    Place::Cycle(vec2(3., 0.), 2., 1),
+                    Place::Pickup(vec2(2., 0.43), 100),
+                    Place::Finish(vec2(3.5, 0.)),
+                ],
+                ..default()
+            },
+            LevelDef {
+                places: vec![
+                    Place::Baton(vec2(-0.5, 0.)),
+                    Place::CycleStart(vec2(0., 0.), 0.5),
+                    Place::Cycle(vec2(1., 0.), 1., 1),
+                    Place::Cycle(vec2(2., 0.), 1.5, 1),
+                    Place::Cycle(vec2(3., 0.), 2., 1),
+                    Place::Cycle(vec2(4., 0.), 2.5, 1),
+                    Place::Cycle(vec2(5., 0.), 3., 1),
+                    Place::Pickup(vec2(2., 0.43), 100),
+                    Place::Pickup(vec2(4., -0.43), 100),
+                    Place::Finish(vec2(5.5, 0.)),
+                ],
+                skins: vec![(5, HandSkin::Thumb)],
+                ..default()
+            },
+            LevelDef {
+                places: vec![
+                    Place::Baton(vec2(-0.5, 0.)),
+                    Place::CycleStart(vec2(0., 0.), 0.5),
+                    Place::Cycle(vec2(1., 0.), 1., 1),
+                    Place::Cycle(vec2(2., 0.), 1.5, 1),
+                    Place::Cycle(vec2(3., 0.), 2., 1),
+                    Place::CycleMoving(vec2(4., -0.25), vec2(4., 0.25), 4., 2.5),
+                    Place::Cycle(vec2(5., 0.), 3., 1),
+                    Place::Cycle(vec2(6., 0.), 3.5, 2),
+                    Place::Cycle(vec2(7., 0.), 4., 1),
+                    Place::Cycle(vec2(8., 0.), 4.5, 2),
+                    Place::Cycle(vec2(9., 0.), 5., 3),
+                    Place::Hazard(vec2(6.5, 0.3), 48.),
+                    Place::Pickup(vec2(3., 0.43), 100),
+                    Place::Pickup(vec2(8., -0.43), 250),
+                    Place::Finish(vec2(9.5, 0.)),
+                ],
+                skins: vec![(6, HandSkin::Thumb), (9, HandSkin::Thumb)],
+                baton_weight: 0.25,
+                ..default()
+            },
+        ])
     }
 }
 
-fn system_lerp_camera_to_focus(
-    focus: Res<CameraFocus>,
-    mut query: Query<&mut Transform, With<Camera>>,
+#[derive(Resource, Default)]
+pub struct CurrentLevel(pub usize);
+
+fn system_apply_launch_options(
+    launch: Res<LaunchOptions>,
+    levels: Res<Levels>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut difficulty: ResMut<Difficulty>,
+    mut seed: ResMut<LevelSeed>,
+    mut mode: ResMut<GameMode>,
 ) {
-    for mut transform in &mut query {
-        let target = focus.0.extend(transform.translation.z);
-        transform.translation = transform.translation.lerp(target, 0.05);
+    if let Some(level) = launch.level {
+        current_level.0 = level.min(levels.0.len() - 1);
+    }
+    if let Some(launch_difficulty) = launch.difficulty {
+        *difficulty = launch_difficulty;
+    }
+    if let Some(launch_seed) = launch.seed {
+        seed.0 = launch_seed;
+    }
+    if let Some(launch_mode) = launch.mode {
+        *mode = launch_mode;
     }
 }
 
-fn on_add_active(
-    trigger: Trigger<OnAdd, Active>,
-    child_query: Query<(&GlobalTransform, Option<&Parent>), With<Active>>,
-    parent_query: Query<&GlobalTransform, Without<Active>>,
-    mut focus: ResMut<CameraFocus>,
+/// Marks the root entities of a level so they can be torn down between levels.
+#[derive(Component)]
+struct LevelEntity;
+
+fn spawn_level(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    level: &LevelDef,
+    difficulty: Difficulty,
 ) {
-    let Ok((fallback, parent)) = child_query.get(trigger.entity()) else {
-        return;
-    };
+    let cycle_image = &assets.cycle;
+    let finish_image = &assets.finish;
+    let baton_image = &assets.baton;
 
-    match parent {
-        Some(parent) => {
-            let Ok(transform) = parent_query.get(parent.get()) else {
-                return;
-            };
+    let mut cycle_index = 0;
+    for place in &level.places {
+        match place {
+            Place::Cycle(position, speed, _)
+            | Place::CycleStart(position, speed)
+            | Place::CycleMoving(position, _, _, speed) => {
+                let skin = level
+                    .skins
+                    .iter()
+                    .find(|(index, _)| *index == cycle_index)
+                    .map_or(HandSkin::default(), |(_, skin)| *skin);
+                let hand_sprites = assets.hand_sprites(skin);
+                let color = level.first_color + cycle_index;
+                cycle_index += 1;
 
-            focus.0 = transform.translation().xy();
-        }
-        None => {
-            focus.0 = fallback.translation().xy();
-        }
+                let mut cycle = commands.spawn((
+                    CycleBundle::new(cycle_image).translation(*position * CYCLE_UNIT),
+                    CycleColor(color),
+                    LevelEntity,
+                ));
+
+                if let Place::CycleMoving(from, to, period, _) = place {
+                    cycle.insert(CycleMotion {
+                        from: *from * CYCLE_UNIT,
+                        to: *to * CYCLE_UNIT,
+                        period: *period,
+                        elapsed: 0.,
+                    });
+                }
+
+                let hands = match place {
+                    Place::Cycle(_, _, hands) => (*hands).max(1),
+                    _ => 1,
+                };
+
+                cycle.with_children(|parent| {
+                    for i in 0..hands {
+                        let progress = 0.5 + i as f32 / hands as f32;
+                        let mut hand =
+                            parent.spawn(HandBundle::new(hand_sprites.clone()).progress(progress));
+                        hand.insert((
+                            Speed(speed.abs() * difficulty.speed_multiplier()),
+                            Direction(speed.signum()),
+                        ));
+                        // Only one hand in the level may start out active.
+                        if let (Place::CycleStart(_, _), 0) = (place, i) {
+                            hand.insert(Active);
+                        }
+                    }
+                });
+            }
+            Place::Finish(position) => {
+                commands.spawn((
+                    Finish,
+                    Collision::Circle(Circle::new(64.)),
+                    SimPosition(*position * CYCLE_UNIT),
+                    Speed(0.),
+                    SpriteBundle {
+                        texture: finish_image.clone(),
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(64.0)),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(position.extend(1.) * CYCLE_UNIT),
+                        ..default()
+                    },
+                    CanHold,
+                    Grip::Empty,
+                    RenderLayers::layer(LAYER_INACTIVE),
+                    LevelEntity,
+                ));
+            }
+            Place::Hazard(position, radius) => {
+                commands.spawn((
+                    Hazard,
+                    Collision::Circle(Circle::new(*radius)),
+                    SimPosition(*position * CYCLE_UNIT),
+                    SpriteBundle {
+                        texture: cycle_image.clone(),
+                        sprite: Sprite {
+                            color: TINT_HAZARD,
+                            custom_size: Some(Vec2::splat(radius * 2.)),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(
+                            (*position * CYCLE_UNIT).extend(0.5),
+                        ),
+                        ..default()
+                    },
+                    RenderLayers::layer(LAYER_ACTIVE),
+                    LevelEntity,
+                ));
+            }
+            Place::Pickup(position, value) => {
+                commands.spawn((
+                    Pickup { value: *value },
+                    Collision::Circle(Circle::new(PICKUP_SIZE / 2.)),
+                    SimPosition(*position * CYCLE_UNIT),
+                    SpriteBundle {
+                        texture: cycle_image.clone(),
+                        sprite: Sprite {
+                            color: TINT_PICKUP,
+                            custom_size: Some(Vec2::splat(PICKUP_SIZE)),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(
+                            (*position * CYCLE_UNIT).extend(0.5),
+                        ),
+                        ..default()
+                    },
+                    RenderLayers::layer(LAYER_ACTIVE),
+                    LevelEntity,
+                ));
+            }
+            Place::Baton(position) => {
+                commands.spawn((
+                    Item,
+                    Weight(level.baton_weight),
+                    Collision::Circle(Circle::new(40.)),
+                    SimPosition(*position * CYCLE_UNIT),
+                    ColliderScale(1.),
+                    SpriteBundle {
+                        texture: baton_image.clone(),
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(BATON_SIZE)),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(position.extend(1.) * CYCLE_UNIT),
+                        ..default()
+                    },
+                    RenderLayers::layer(LAYER_INACTIVE),
+                    LevelEntity,
+                ));
+            }
+        };
     }
 }
 
-fn on_remove_grab(
-    trigger: Trigger<OnRemove, Holding>,
-    asset_server: Res<AssetServer>,
-    mut query: Query<&mut Handle<Image>, With<Hand>>,
-) {
-    if let Ok(mut sprite) = query.get_mut(trigger.entity()) {
-        *sprite = asset_server.load("hand-open.png");
-    }
+/// Every texture and sound the game uses, loaded up front while in [`Game::Loading`].
+#[derive(Resource)]
+pub struct GameAssets {
+    hand_open: Handle<Image>,
+    hand_closed: Handle<Image>,
+    hand_closed_thumb: Handle<Image>,
+    /// Empty until the hand images above have loaded.
+    hand_atlas: Option<HandAtlas>,
+    cycle: Handle<Image>,
+    finish: Handle<Image>,
+    baton: Handle<Image>,
+    hand_over_sfx: Handle<AudioSource>,
+    select_sfx: Handle<AudioSource>,
+    select_miss_sfx: Handle<AudioSource>,
+    finish_sfx: Handle<AudioSource>,
+    music: Handle<AudioSource>,
+    /// Font used for banners. Bevy's built-in font unless a theme swaps it out.
+    pub font: Handle<Font>,
 }
 
-fn on_add_grab(
-    trigger: Trigger<OnAdd, Holding>,
-    asset_server: Res<AssetServer>,
-    mut query: Query<&mut Handle<Image>, With<Hand>>,
-) {
-    if let Ok(mut sprite) = query.get_mut(trigger.entity()) {
-        *sprite = asset_server.load("hand-closed.png");
+impl FromWorld for GameAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            hand_open: asset_server.load("hand-open.png"),
+            hand_closed: asset_server.load("hand-closed.png"),
+            hand_closed_thumb: asset_server.load("hand-closed-thumb.png"),
+            hand_atlas: None,
+            cycle: asset_server.load("cycle.png"),
+            finish: asset_server.load("finish.png"),
+            baton: asset_server.load("statue.png"),
+            hand_over_sfx: asset_server.load("hand-over.wav"),
+            select_sfx: asset_server.load("select.wav"),
+            select_miss_sfx: asset_server.load("select-miss.wav"),
+            finish_sfx: asset_server.load("finish.mp3"),
+            music: asset_server.load("music.wav"),
+            font: Handle::default(),
+        }
     }
 }
 
-fn on_finish(
-    trigger: Trigger<OnAdd, Active>,
-    mut state: ResMut<NextState<Game>>,
-    query: Query<&Finish>,
-) {
-    if query.get(trigger.entity()).is_ok() {
-        state.set(Game::Finished);
+impl GameAssets {
+    /// Assets the game waits for before starting. Music may start late, so it isn't waited on.
+    fn required(&self) -> [UntypedAssetId; 10] {
+        [
+            self.hand_open.id().untyped(),
+            self.hand_closed.id().untyped(),
+            self.hand_closed_thumb.id().untyped(),
+            self.cycle.id().untyped(),
+            self.finish.id().untyped(),
+            self.baton.id().untyped(),
+            self.hand_over_sfx.id().untyped(),
+            self.select_sfx.id().untyped(),
+            self.select_miss_sfx.id().untyped(),
+            self.finish_sfx.id().untyped(),
+        ]
     }
-}
 
-fn system_fade_out_everything(mut query: Query<&mut Sprite, Without<Item>>) {
-    for mut sprite in &mut query {
-        let next_alpha = sprite.color.alpha().lerp(0., 0.1);
-        sprite.color.set_alpha(next_alpha);
+    /// Packs the hand images into [`HandAtlas`], at the `HAND_FRAME_*` indices.
+    fn build_hand_atlas(
+        &mut self,
+        images: &mut Assets<Image>,
+        layouts: &mut Assets<TextureAtlasLayout>,
+    ) {
+        let mut builder = TextureAtlasBuilder::default();
+        builder.padding(UVec2::splat(HAND_ATLAS_PADDING));
+        for handle in [&self.hand_open, &self.hand_closed, &self.hand_closed_thumb] {
+            let Some(image) = images.get(handle) else {
+                return;
+            };
+            builder.add_texture(Some(handle.id()), image);
+        }
+
+        match builder.build() {
+            Ok((layout, image)) => {
+                self.hand_atlas = Some(HandAtlas {
+                    image: images.add(image),
+                    layout: layouts.add(layout),
+                });
+            }
+            Err(error) => warn!("Could not build the hand atlas: {error}"),
+        }
     }
-}
 
-fn system_magnify_baton(mut query: Query<&mut Sprite, With<Item>>) {
-    for mut sprite in &mut query {
-        if let Some(previous_size) = sprite.custom_size {
-            let next_size = previous_size.lerp(vec2(256., 256.), 0.1);
-            sprite.custom_size = Some(next_size);
+    fn hand_sprites(&self, skin: HandSkin) -> HandSprites {
+        let closed = match skin {
+            HandSkin::Plain => HAND_FRAME_CLOSED,
+            HandSkin::Thumb => HAND_FRAME_CLOSED_THUMB,
+        };
+        HandSprites {
+            atlas: self.hand_atlas.clone().unwrap_or_default(),
+            frames: vec![HAND_FRAME_OPEN, closed],
         }
     }
 }
 
-fn system_show_finish_text(mut commands: Commands) {
+#[derive(Component)]
+struct LoadingScreen;
+
+#[derive(Component)]
+struct LoadingBar;
+
+#[derive(Component)]
+struct LoadingErrorText;
+
+fn system_show_loading(mut commands: Commands) {
     commands
-        .spawn(NodeBundle {
-            style: Style {
-                position_type: PositionType::Absolute,
-                top: Val::Percent(10.),
-                left: Val::Px(0.),
-                right: Val::Px(0.),
-                bottom: Val::Px(0.),
-                display: Display::Flex,
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Start,
+        .spawn((
+            LoadingScreen,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(16.),
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
                 ..default()
             },
-            ..default()
-        })
+        ))
         .with_children(|parent| {
-            parent.spawn(TextBundle {
-                text: Text::from_section(
-                    "LIBERTY ACHIEVED",
+            parent.spawn(TextBundle::from_section(
+                "LOADING",
+                TextStyle {
+                    font_size: 48.,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(320.),
+                        height: Val::Px(16.),
+                        ..default()
+                    },
+                    background_color: Color::Srgba(GRAY).into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        LoadingBar,
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.),
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            background_color: Color::WHITE.into(),
+                            ..default()
+                        },
+                    ));
+                });
+            parent.spawn((
+                LoadingErrorText,
+                TextBundle::from_section(
+                    "",
                     TextStyle {
-                        font_size: 64.,
-                        color: Color::WHITE,
+                        font_size: 24.,
+                        color: Color::Srgba(RED_100),
                         ..default()
                     },
-                ),
-                ..default()
-            });
+                )
+                .with_text_justify(JustifyText::Center),
+            ));
         });
 }
 
-fn system_play_finish_sound(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let finish = asset_server.load("finish.mp3");
-    commands.spawn(AudioBundle {
-        source: finish.clone(),
-        ..default()
-    });
+/// Lists assets that failed to load instead of waiting on them forever.
+fn system_check_asset_failures(
+    asset_server: Res<AssetServer>,
+    assets: Res<GameAssets>,
+    mut texts: Query<&mut Text, With<LoadingErrorText>>,
+) {
+    let failed = assets
+        .required()
+        .into_iter()
+        .filter(|id| matches!(asset_server.get_load_state(*id), Some(LoadState::Failed(_))))
+        .map(|id| match asset_server.get_path(id) {
+            Some(path) => path.to_string(),
+            None => format!("{id:?}"),
+        })
+        .collect::<Vec<_>>();
+
+    if failed.is_empty() {
+        return;
+    }
+
+    let message = format!("COULD NOT LOAD\n{}", failed.join("\n"));
+    for mut text in &mut texts {
+        if text.sections[0].value != message {
+            error!("{message}");
+            text.sections[0].value.clone_from(&message);
+        }
+    }
 }
 
-pub struct GameBundle;
+fn system_check_loading(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut assets: ResMut<GameAssets>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut source: LevelSource,
+    tutorial_done: Res<TutorialDone>,
+    launch: Res<LaunchOptions>,
+    mut state: ResMut<NextState<Game>>,
+    mut bars: Query<&mut Style, With<LoadingBar>>,
+) {
+    let required = assets.required();
+    let loaded = required
+        .iter()
+        .filter(|id| matches!(asset_server.get_load_state(**id), Some(LoadState::Loaded)))
+        .count();
 
-impl Plugin for GameBundle {
-    fn build(&self, app: &mut App) {
-        app.init_resource::<Overlap>()
-            .add_event::<GameEvent>()
-            .observe(on_add_active)
-            .observe(on_add_grab)
-            .observe(on_remove_grab)
-            .observe(on_finish)
-            .init_resource::<CameraFocus>()
-            .insert_state(Game::Playing)
-            .add_systems(Startup, system_setup_camera)
-            .add_systems(Startup, system_setup_entities)
-            .add_systems(PreUpdate, system_check_overlap)
-            .add_systems(Update, system_cycle_hand.run_if(in_state(Game::Playing)))
-            .add_systems(Update, system_progress.run_if(in_state(Game::Playing)))
-            .add_systems(Update, system_grab_toggle.run_if(in_state(Game::Playing)))
-            .add_systems(Update, system_tint_layers.run_if(in_state(Game::Playing)))
-            .add_systems(Update, system_play_sfx)
-            .add_systems(Update, system_lerp_camera_to_focus)
-            .add_systems(Update, system_lerp_item_to_holding)
-            .add_systems(Update, system_set_render_layer)
-            .add_systems(Update, system_after_images)
-            .add_systems(Update, fade_out_after_images)
+    for mut style in &mut bars {
+        style.width = Val::Percent(loaded as f32 / required.len() as f32 * 100.);
+    }
+
+    if loaded < required.len() {
+        return;
+    }
+
+    if assets.hand_atlas.is_none() {
+        assets.build_hand_atlas(&mut images, &mut layouts);
+    }
+
+    // The tutorial hasn't been played through yet, so start with it instead.
+    if !tutorial_done.done && launch.level.is_none() {
+        state.set(Game::Tutorial);
+        return;
+    }
+
+    source.spawn(&mut commands, &assets);
+    state.set(Game::Countdown);
+}
+
+fn system_hide_loading(mut commands: Commands, query: Query<Entity, With<LoadingScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+const CYCLE_COLOR: [Color; 8] = [
+    Color::Srgba(GREEN_100),
+    Color::Srgba(RED_100),
+    Color::Srgba(PURPLE_100),
+    Color::Srgba(YELLOW_100),
+    Color::Srgba(BLUE_100),
+    Color::Srgba(TEAL_100),
+    Color::Srgba(ORANGE_100),
+    Color::Srgba(PINK_100),
+];
+
+/// Okabe-Ito colors, which stay distinguishable with the common kinds of color blindness.
+const HIGH_CONTRAST_COLOR: [Color; 7] = [
+    Color::srgb(0.9, 0.62, 0.),
+    Color::srgb(0.34, 0.71, 0.91),
+    Color::srgb(0., 0.62, 0.45),
+    Color::srgb(0.94, 0.89, 0.26),
+    Color::srgb(0., 0.45, 0.7),
+    Color::srgb(0.84, 0.37, 0.),
+    Color::srgb(0.8, 0.47, 0.65),
+];
+
+/// Colors used to tell active things apart from the rest, and to paint after-images.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    #[default]
+    Default,
+    HighContrast,
+}
+
+impl ColorScheme {
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorScheme::Default => "DEFAULT",
+            ColorScheme::HighContrast => "HIGH CONTRAST",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            ColorScheme::Default => ColorScheme::HighContrast,
+            ColorScheme::HighContrast => ColorScheme::Default,
+        }
+    }
+
+    fn active(self) -> Color {
+        match self {
+            ColorScheme::Default => TINT_ACTIVE,
+            ColorScheme::HighContrast => Color::srgb(0.94, 0.89, 0.26),
+        }
+    }
+
+    fn inactive(self) -> Color {
+        match self {
+            ColorScheme::Default => TINT_INACTIVE,
+            ColorScheme::HighContrast => Color::srgb(0.25, 0.25, 0.25),
+        }
+    }
+
+    fn after_images(self) -> Vec<Color> {
+        match self {
+            ColorScheme::Default => CYCLE_COLOR.to_vec(),
+            ColorScheme::HighContrast => HIGH_CONTRAST_COLOR.to_vec(),
+        }
+    }
+}
+
+#[derive(Resource)]
+struct AfterImagePalette(Vec<Color>);
+
+impl Default for AfterImagePalette {
+    fn default() -> Self {
+        Self(CYCLE_COLOR.to_vec())
+    }
+}
+
+fn system_apply_color_scheme(scheme: Res<ColorScheme>, mut palette: ResMut<AfterImagePalette>) {
+    if scheme.is_changed() {
+        palette.0 = scheme.after_images();
+    }
+}
+
+const AFTER_IMAGE_CAP: usize = 64;
+
+/// Live after-images, oldest first, so the oldest can be dropped once over the cap.
+#[derive(Resource, Default)]
+struct AfterImageRing(VecDeque<Entity>);
+
+/// Most hidden after-images kept for reuse, any more are despawned.
+const AFTER_IMAGE_POOL_CAP: usize = 64;
+
+/// Faded after-images, hidden until they are shown again in place of spawning new ones.
+#[derive(Resource, Default)]
+struct AfterImagePool(Vec<Entity>);
+
+impl AfterImagePool {
+    /// Shows a pooled after-image as `bundle`, spawning a new one if the pool is empty.
+    fn acquire(&mut self, commands: &mut Commands, bundle: impl Bundle) -> Entity {
+        match self.0.pop() {
+            Some(entity) => commands.entity(entity).insert(bundle).id(),
+            None => commands.spawn(bundle).id(),
+        }
+    }
+
+    fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        if self.0.contains(&entity) {
+            return;
+        }
+
+        if self.0.len() < AFTER_IMAGE_POOL_CAP {
+            commands.entity(entity).insert(Visibility::Hidden);
+            self.0.push(entity);
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+const AFTER_IMAGE_INTERVAL_MIN: f32 = 16.;
+const AFTER_IMAGE_INTERVAL_MAX: f32 = 500.;
+
+fn after_image_interval(speed: f32) -> Duration {
+    let millis = 10. / speed.abs().max(f32::EPSILON);
+    Duration::from_millis(millis.clamp(AFTER_IMAGE_INTERVAL_MIN, AFTER_IMAGE_INTERVAL_MAX) as u64)
+}
+
+fn system_after_images(
+    mut index: Local<usize>,
+    mut timer: Local<Timer>,
+    time: Res<Time>,
+    query: Query<(&GlobalTransform, &Speed, Ref<Grip>, Option<&HandSprites>), With<Active>>,
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    combo: Res<Combo>,
+    palette: Res<AfterImagePalette>,
+    mut live: ResMut<AfterImageRing>,
+    mut pool: ResMut<AfterImagePool>,
+) {
+    let Ok((global_transform, Speed(speed), grip, hand_sprites)) = query.get_single() else {
+        return;
+    };
+
+    if !matches!(*grip, Grip::Holding(_)) {
+        return;
+    }
+
+    if grip.is_changed() {
+        timer.set_duration(after_image_interval(*speed));
+        timer.reset();
+    };
+
+    timer.tick(time.delta());
+
+    if timer.finished() && !palette.0.is_empty() {
+        let mut color = palette.0[*index % palette.0.len()];
+        color.set_alpha(0.7);
+
+        // The finish holds the baton at the very end too, and leaves plain closed hands behind.
+        let hand_sprites = hand_sprites
+            .cloned()
+            .unwrap_or_else(|| assets.hand_sprites(HandSkin::default()));
+        let after_image = pool.acquire(
+            &mut commands,
+            (
+                AfterImage,
+                FadeOutSpeed(3. * speed.abs()),
+                hand_sprites.texture_atlas(hand_sprites.closed()),
+                SpriteBundle {
+                    texture: hand_sprites.atlas.image.clone(),
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::splat(64.0)),
+                        color,
+                        ..default()
+                    },
+                    transform: Transform::from_translation(global_transform.translation()),
+                    ..default()
+                },
+            ),
+        );
+
+        live.0.push_back(after_image);
+        while live.0.len() > AFTER_IMAGE_CAP {
+            if let Some(oldest) = live.0.pop_front() {
+                pool.release(&mut commands, oldest);
+            }
+        }
+
+        let step = 1 + (combo.count / COMBO_PALETTE_STEP) as usize;
+        *index = (*index + step) % palette.0.len();
+        timer.reset();
+    }
+}
+
+const BATON_TRAIL_INTERVAL: Duration = Duration::from_millis(40);
+
+#[derive(Component)]
+struct BatonTrail;
+
+fn system_baton_trail(
+    mut timer: Local<Timer>,
+    time: Res<Time>,
+    holders: Query<&Grip, With<Active>>,
+    items: Query<(&GlobalTransform, &Handle<Image>, &Sprite), With<Item>>,
+    mut commands: Commands,
+) {
+    let Ok(Grip::Holding(item)) = holders.get_single() else {
+        return;
+    };
+
+    let Ok((global_transform, texture, sprite)) = items.get(*item) else {
+        return;
+    };
+
+    if timer.duration() != BATON_TRAIL_INTERVAL {
+        *timer = Timer::new(BATON_TRAIL_INTERVAL, TimerMode::Repeating);
+    }
+
+    if !timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    commands.spawn((
+        AfterImage,
+        BatonTrail,
+        FadeOutSpeed(2.),
+        SpriteBundle {
+            texture: texture.clone(),
+            sprite: Sprite {
+                custom_size: sprite.custom_size,
+                color: Color::WHITE.with_alpha(0.5),
+                ..default()
+            },
+            transform: Transform::from_translation(global_transform.translation()),
+            ..default()
+        },
+    ));
+}
+
+/// How far past the edge of the view an after-image may be before it is culled,
+/// at least its own size so it never disappears while partly visible.
+const AFTER_IMAGE_CULL_MARGIN: f32 = 64.;
+
+fn fade_out_after_images(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut live: ResMut<AfterImageRing>,
+    mut pool: ResMut<AfterImagePool>,
+    cameras: Query<(&GlobalTransform, &OrthographicProjection), With<Camera>>,
+    mut query: Query<
+        (
+            Entity,
+            &FadeOutSpeed,
+            &mut Sprite,
+            &Transform,
+            &Visibility,
+            Has<BatonTrail>,
+        ),
+        With<AfterImage>,
+    >,
+) {
+    // After-images left behind by a moving camera are dropped instead of fading unseen.
+    let view = cameras.iter().next().map(|(camera, projection)| {
+        let center = camera.translation().xy();
+        Rect::from_corners(projection.area.min + center, projection.area.max + center)
+            .inflate(AFTER_IMAGE_CULL_MARGIN)
+    });
+
+    for (entity, FadeOutSpeed(speed), mut sprite, transform, visibility, is_trail) in &mut query {
+        if visibility == Visibility::Hidden {
+            continue;
+        }
+
+        let next_alpha = sprite.color.alpha() - speed * time.delta_seconds();
+        if let Some(next_size) = sprite.custom_size {
+            let next_size = next_size - Vec2::splat(64. * speed * time.delta_seconds());
+            sprite.custom_size = Some(next_size.max(Vec2::ZERO));
+        }
+        sprite.color.set_alpha(next_alpha);
+
+        let shrunk = sprite
+            .custom_size
+            .is_some_and(|size| size.cmple(Vec2::ZERO).any());
+        let offscreen = view.is_some_and(|view| !view.contains(transform.translation.xy()));
+        if sprite.color.alpha() <= 0. || shrunk || offscreen {
+            if is_trail {
+                commands.entity(entity).despawn();
+            } else {
+                live.0.retain(|live| *live != entity);
+                pool.release(&mut commands, entity);
+            }
+        }
+    }
+}
+
+/// How heavy a baton is, slowing the hand that holds it.
+#[derive(Component, Clone, Copy)]
+struct Weight(f32);
+
+/// How many times slower a hand turns while holding something of `weight`.
+fn weight_slowdown(weight: f32) -> f32 {
+    1. + weight.max(0.)
+}
+
+fn system_store_previous_progress(mut query: Query<(&mut PreviousProgress, &Progress)>) {
+    for (mut previous, progress) in query.iter_mut() {
+        previous.0 = progress.0;
+    }
+}
+
+fn system_progress(
+    mut query: Query<(&mut Progress, &Speed, &Direction, &Grip), With<Active>>,
+    weights: Query<&Weight>,
+    time: Res<Time>,
+) {
+    for (mut progress, Speed(speed), Direction(direction), grip) in query.iter_mut() {
+        progress.advance(match grip {
+            Grip::Holding(item) => {
+                let weight = weights.get(*item).map_or(0., |Weight(weight)| *weight);
+                time.delta_seconds() * speed * direction / weight_slowdown(weight)
+            }
+            _ => time.delta_seconds() * 0.5 * direction, // slower speed to pick up baton again
+        });
+    }
+}
+
+/// Pairs of colliders touching this fixed step. Stable.
+#[derive(Resource, Default)]
+pub struct Overlap {
+    overlaps: Vec<(Entity, Entity)>,
+}
+
+impl Overlap {
+    /// Forgets all pairs but keeps the buffer around for the next check.
+    fn clear(&mut self) {
+        self.overlaps.clear();
+    }
+
+    fn insert(&mut self, e1: Entity, e2: Entity) {
+        self.overlaps.push((e1, e2));
+        self.overlaps.push((e2, e1));
+    }
+
+    /// Every overlapping pair, each listed in both orders.
+    pub fn pairs(&self) -> &[(Entity, Entity)] {
+        &self.overlaps
+    }
+
+    pub fn between(&self, a: Entity, b: Entity) -> bool {
+        self.overlaps.contains(&(a, b))
+    }
+
+    fn with(&self, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.overlaps
+            .iter()
+            .filter_map(move |(e1, e2)| if *e1 == entity { Some(*e2) } else { None })
+    }
+}
+
+/// Places every collider where the simulation has it this step: hands from their cycle
+/// and progress, held items on their holder, and the rest where they stand.
+fn system_place_colliders(
+    cycles: Query<(&Transform, &Radius), With<Cycle>>,
+    mut hands: Query<(&Progress, &Parent, &Grip, &mut SimPosition), With<Hand>>,
+    mut others: Query<(&Transform, Option<&Grip>, &mut SimPosition), Without<Hand>>,
+) {
+    let mut held = Vec::new();
+
+    for (progress, parent, grip, mut position) in &mut hands {
+        if let Ok((cycle, radius)) = cycles.get(parent.get()) {
+            let angle = progress.angle();
+            position.0 = cycle.translation.xy() + Vec2::new(angle.cos(), angle.sin()) * radius.0;
+        }
+        if let Grip::Holding(item) = grip {
+            held.push((*item, position.0));
+        }
+    }
+
+    // Apart from held items, which are moved onto their holder below, these are all roots.
+    for (transform, grip, mut position) in &mut others {
+        position.0 = transform.translation.xy();
+        if let Some(Grip::Holding(item)) = grip {
+            held.push((*item, position.0));
+        }
+    }
+
+    // A held item is wherever its holder is, however far its sprite still has to ease.
+    for (item, holder) in held {
+        if let Ok((_, _, mut position)) = others.get_mut(item) {
+            position.0 = holder;
+        }
+    }
+}
+
+fn system_check_overlap(
+    query: Query<(Entity, &SimPosition, &Collision, Option<&ColliderScale>)>,
+    mut current_overlap: ResMut<Overlap>,
+) {
+    current_overlap.clear();
+
+    for [(e1, p1, c1, s1), (e2, p2, c2, s2)] in query.iter_combinations() {
+        let bc1 = c1.bounding_circle(p1.0, s1);
+        let bc2 = c2.bounding_circle(p2.0, s2);
+
+        if bc1.intersects(&bc2) {
+            current_overlap.insert(e1, e2);
+        }
+    }
+}
+
+/// When set, the baton can only be passed to a hand that is closer to the finish.
+#[derive(Resource, Default)]
+struct DirectionalHandover(bool);
+
+/// How long a missed grab press is remembered while waiting for a valid overlap.
+const GRAB_BUFFER_WINDOW: Duration = Duration::from_millis(150);
+
+#[derive(Resource)]
+struct GrabBuffer {
+    timer: Timer,
+}
+
+impl Default for GrabBuffer {
+    fn default() -> Self {
+        let mut timer = Timer::new(GRAB_BUFFER_WINDOW, TimerMode::Once);
+        timer.tick(GRAB_BUFFER_WINDOW);
+        Self { timer }
+    }
+}
+
+impl GrabBuffer {
+    fn clear(&mut self) {
+        let remaining = self.timer.remaining();
+        self.timer.tick(remaining);
+    }
+}
+
+/// How far apart two colliders may be for a missed grab to still count as a near miss.
+const NEAR_MISS_MARGIN: f32 = 24.;
+/// Real time seconds the game runs in slow motion after a near miss.
+const SLOW_MO_DURATION: f32 = 0.3;
+const SLOW_MO_SPEED: f32 = 0.25;
+
+#[derive(SystemParam)]
+struct GrabCandidates<'w, 's> {
+    overlap: Res<'w, Overlap>,
+    directional: Res<'w, DirectionalHandover>,
+    hand_overs: Query<'w, 's, (Entity, &'static SimPosition), (With<CanHold>, Without<Active>)>,
+    items: Query<'w, 's, (), With<Item>>,
+    parents: Query<'w, 's, &'static Parent>,
+    cycles: Query<'w, 's, &'static Transform, With<Cycle>>,
+    finishes: Query<'w, 's, &'static SimPosition, With<Finish>>,
+    colliders: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static SimPosition,
+            &'static Collision,
+            Option<&'static ColliderScale>,
+        ),
+    >,
+}
+
+impl GrabCandidates<'_, '_> {
+    /// Hands are judged by the center of their cycle, everything else by itself.
+    fn station(&self, entity: Entity, position: &SimPosition) -> Vec2 {
+        self.parents
+            .get(entity)
+            .ok()
+            .and_then(|parent| self.cycles.get(parent.get()).ok())
+            .map_or(position.0, |cycle| cycle.translation.xy())
+    }
+
+    fn is_forward(
+        &self,
+        (from, from_position): (Entity, &SimPosition),
+        (to, to_position): (Entity, &SimPosition),
+    ) -> bool {
+        match self.finishes.iter().next() {
+            Some(finish) if self.directional.0 => {
+                self.station(to, to_position).distance(finish.0)
+                    < self.station(from, from_position).distance(finish.0)
+            }
+            _ => true,
+        }
+    }
+
+    /// What a grab by the active hand would act on: the hand to pass to while
+    /// holding the baton, or the item to pick up while empty handed.
+    fn candidate(&self, entity: Entity, position: &SimPosition, grip: &Grip) -> Option<Entity> {
+        match grip {
+            Grip::Holding(_) => self.overlap.with(entity).find(|e| {
+                self.hand_overs
+                    .get(*e)
+                    .is_ok_and(|(other, other_position)| {
+                        self.is_forward((entity, position), (other, other_position))
+                    })
+            }),
+            Grip::Reaching => None,
+            Grip::Empty => self.overlap.with(entity).find(|e| self.items.contains(*e)),
+        }
+    }
+
+    /// Whether something the active hand could have acted on was just out of reach.
+    fn near_miss(&self, entity: Entity, position: &SimPosition, grip: &Grip) -> bool {
+        let Ok((_, own_position, own_collision, own_scale)) = self.colliders.get(entity) else {
+            return false;
+        };
+        let own = own_collision.bounding_circle(own_position.0, own_scale);
+
+        self.colliders
+            .iter()
+            .filter(|(other, ..)| match grip {
+                Grip::Holding(_) => {
+                    self.hand_overs
+                        .get(*other)
+                        .is_ok_and(|(other, other_position)| {
+                            self.is_forward((entity, position), (other, other_position))
+                        })
+                }
+                Grip::Reaching => false,
+                Grip::Empty => self.items.contains(*other),
+            })
+            .any(|(_, other_position, collision, scale)| {
+                let other = collision.bounding_circle(other_position.0, scale);
+                let gap = own.center().distance(other.center()) - own.radius() - other.radius();
+                gap <= NEAR_MISS_MARGIN
+            })
+    }
+}
+
+/// Counts down in real time while the game is slowed after a near miss.
+#[derive(Resource, Default)]
+struct SlowMoTimer(Option<Timer>);
+
+fn system_slow_mo(
+    mut slow_mo: ResMut<SlowMoTimer>,
+    mut speed_before: Local<Option<f32>>,
+    real: Res<Time<Real>>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let Some(timer) = &mut slow_mo.0 else {
+        return;
+    };
+
+    // Slowed relative to whatever speed was set before, such as a debug time scale,
+    // which is put back once the slow motion is over.
+    let before = *speed_before.get_or_insert(time.relative_speed());
+    if timer.tick(real.delta()).finished() {
+        slow_mo.0 = None;
+        *speed_before = None;
+        time.set_relative_speed(before);
+    } else {
+        time.set_relative_speed(before * SLOW_MO_SPEED);
+    }
+}
+
+/// Presses are latched every frame and handed to the next fixed step, so none are lost
+/// or repeated when a frame runs zero or several fixed steps.
+fn system_latch_presses(action_input: Res<ActionInput>, mut step_input: ResMut<StepInput>) {
+    step_input.latch(action_input.just_pressed_bits());
+}
+
+fn system_buffer_grab(step_input: Res<StepInput>, mut buffer: ResMut<GrabBuffer>) {
+    if step_input.just_pressed(Action::Grab) {
+        buffer.timer.reset();
+    }
+}
+
+/// Assist mode where the active hand grabs by itself whenever there is something to grab.
+#[derive(Resource, Default)]
+pub struct AutoGrab(pub bool);
+
+/// Time after a grab or handover during which further grabs are ignored,
+/// so they don't chain through a whole line of cycles.
+const GRAB_COOLDOWN: Duration = Duration::from_millis(150);
+
+#[derive(Resource)]
+struct GrabCooldown(Timer);
+
+impl Default for GrabCooldown {
+    fn default() -> Self {
+        let mut timer = Timer::new(GRAB_COOLDOWN, TimerMode::Once);
+        timer.tick(GRAB_COOLDOWN);
+        Self(timer)
+    }
+}
+
+fn system_auto_grab(
+    auto_grab: Res<AutoGrab>,
+    cooldown: Res<GrabCooldown>,
+    active: Query<(Entity, &SimPosition, &Grip), (With<CanHold>, With<Active>)>,
+    candidates: GrabCandidates,
+    mut action_input: ResMut<ActionInput>,
+) {
+    if !auto_grab.0 || !cooldown.0.finished() {
+        return;
+    }
+
+    let Ok((entity, position, grip)) = active.get_single() else {
+        return;
+    };
+
+    if candidates.candidate(entity, position, grip).is_some() {
+        action_input.press(Action::Grab);
+    }
+}
+
+fn system_grab_toggle(
+    mut commands: Commands,
+    time: Res<Time>,
+    active: Query<(Entity, &SimPosition, &Direction, &Grip), (With<CanHold>, With<Active>)>,
+    candidates: GrabCandidates,
+    mut buffer: ResMut<GrabBuffer>,
+    mut cooldown: ResMut<GrabCooldown>,
+    mut last_holder: ResMut<LastHolder>,
+    mut slow_mo: ResMut<SlowMoTimer>,
+    mut event_writer: EventWriter<GameEvent>,
+) {
+    cooldown.0.tick(time.delta());
+    if buffer.timer.finished() {
+        return;
+    }
+
+    // Presses right after a grab or handover are ignored, not just automatic ones.
+    if !cooldown.0.finished() {
+        buffer.clear();
+        return;
+    }
+
+    let Ok((entity, position, direction, grip)) = active.get_single() else {
+        return;
+    };
+
+    // Checked against the overlaps found earlier in this same step, so a buffered
+    // press acts on the step the target comes into reach instead of the one after.
+    let candidate = candidates.candidate(entity, position, grip);
+
+    // A press that finds nothing to act on is kept around for a short while, only
+    // counting as a miss, or letting go of the baton, once the buffer runs out.
+    let expired = candidate.is_none() && buffer.timer.tick(time.delta()).finished();
+
+    match grip {
+        Grip::Holding(item) => {
+            let is_overlapping = candidate.and_then(|e| candidates.hand_overs.get(e).ok());
+
+            if let Some((other, other_position)) = is_overlapping {
+                let mut newly_active = commands.entity(other);
+                newly_active.insert(Grip::Holding(*item));
+                newly_active.insert(Active);
+                // The receiving hand always turns the opposite way of the passing one.
+                newly_active.insert(direction.reversed());
+
+                commands.entity(*item).set_parent_in_place(other);
+
+                let mut old_active = commands.entity(entity);
+                old_active.remove::<Active>();
+                old_active.insert(Grip::Empty);
+                buffer.clear();
+                cooldown.0.reset();
+                last_holder.0 = Some(entity);
+
+                let distance = position.0.distance(other_position.0);
+                event_writer.send(GameEvent::HandOver {
+                    quality: Quality::from_distance(distance),
+                });
+            } else if expired {
+                if candidates.near_miss(entity, position, grip) {
+                    slow_mo.0 = Some(Timer::from_seconds(SLOW_MO_DURATION, TimerMode::Once));
+                }
+                commands.entity(*item).remove_parent_in_place();
+                commands.entity(entity).insert(Grip::Empty);
+                event_writer.send(GameEvent::Drop);
+            }
+        }
+        Grip::Reaching => {
+            commands.entity(entity).insert(Grip::Empty);
+            buffer.clear();
+        }
+        Grip::Empty => {
+            if let Some(item) = candidate {
+                commands.entity(item).set_parent_in_place(entity);
+                commands.entity(entity).insert(Grip::Holding(item));
+                buffer.clear();
+                cooldown.0.reset();
+                last_holder.0 = Some(entity);
+                event_writer.send(GameEvent::Grab);
+            } else if expired {
+                if candidates.near_miss(entity, position, grip) {
+                    slow_mo.0 = Some(Timer::from_seconds(SLOW_MO_DURATION, TimerMode::Once));
+                }
+                commands.entity(entity).insert(Grip::Reaching);
+                event_writer.send(GameEvent::GrabEmpty);
+            }
+        }
+    }
+}
+
+/// The hand that passed the baton on most recently, or picked it up if it hasn't been passed yet.
+#[derive(Resource, Default)]
+struct LastHolder(Option<Entity>);
+
+fn system_check_hazards(
+    mut recovering: Local<bool>,
+    mut commands: Commands,
+    overlap: Res<Overlap>,
+    last_holder: Res<LastHolder>,
+    hazards: Query<Entity, With<Hazard>>,
+    holders: Query<(Entity, &Grip), With<Active>>,
+    mut event_writer: EventWriter<GameEvent>,
+) {
+    let Ok((holder, Grip::Holding(item))) = holders.get_single() else {
+        return;
+    };
+
+    // A returned baton may still be moving back through the hazard, ignore it until it's clear.
+    let touching = hazards.iter().any(|hazard| overlap.between(*item, hazard));
+    if !touching || *recovering {
+        *recovering = touching;
+        return;
+    }
+
+    match last_holder.0.filter(|last| *last != holder) {
+        Some(last) => {
+            commands
+                .entity(holder)
+                .remove::<Active>()
+                .insert(Grip::Empty);
+            commands.entity(last).insert((Active, Grip::Holding(*item)));
+            commands.entity(*item).set_parent_in_place(last);
+            event_writer.send(GameEvent::HazardReset);
+            *recovering = true;
+        }
+        // Nobody to go back to, so it is simply dropped.
+        None => {
+            commands.entity(*item).remove_parent_in_place();
+            commands.entity(holder).insert(Grip::Empty);
+            event_writer.send(GameEvent::Drop);
+        }
+    }
+}
+
+fn system_collect_pickups(
+    mut commands: Commands,
+    overlap: Res<Overlap>,
+    holders: Query<&Grip, With<Active>>,
+    pickups: Query<(&Pickup, &SimPosition)>,
+    mut score: ResMut<Score>,
+    mut event_writer: EventWriter<GameEvent>,
+) {
+    // A dropped baton lying on a pickup doesn't collect it.
+    let Ok(Grip::Holding(item)) = holders.get_single() else {
+        return;
+    };
+
+    for entity in overlap.with(*item) {
+        let Ok((pickup, position)) = pickups.get(entity) else {
+            continue;
+        };
+
+        commands.entity(entity).despawn();
+        score.0 += pickup.value;
+        event_writer.send(GameEvent::Pickup {
+            value: pickup.value,
+            position: position.0,
+        });
+    }
+}
+
+/// Seconds a score popup floats up before it is gone.
+const SCORE_POPUP_LIFETIME: f32 = 0.8;
+/// Screen pixels a score popup rises over its lifetime.
+const SCORE_POPUP_RISE: f32 = 48.;
+
+/// Floating text over the spot in the world where points were earned.
+#[derive(Component)]
+struct ScorePopup {
+    timer: Timer,
+    position: Vec2,
+}
+
+fn system_spawn_score_popups(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut events: EventReader<GameEvent>,
+    active: Query<&GlobalTransform, (With<Hand>, With<Active>)>,
+) {
+    for event in events.read() {
+        let (text, position) = match event {
+            GameEvent::Pickup { value, position } => (format!("+{value}"), *position),
+            // By now the receiving hand is active, right where the handover happened.
+            GameEvent::HandOver {
+                quality: Quality::Perfect,
+            } => {
+                let Ok(hand) = active.get_single() else {
+                    continue;
+                };
+                ("PERFECT".to_string(), hand.translation().xy())
+            }
+            _ => continue,
+        };
+
+        commands.spawn((
+            ScorePopup {
+                timer: Timer::from_seconds(SCORE_POPUP_LIFETIME, TimerMode::Once),
+                position,
+            },
+            TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: assets.font.clone(),
+                    font_size: 32.,
+                    color: TINT_PICKUP,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                ..default()
+            }),
+            StateScoped(AppState::InGame),
+        ));
+    }
+}
+
+fn system_animate_score_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut popups: Query<(Entity, &mut ScorePopup, &mut Style, &mut Text)>,
+) {
+    let Some((camera, camera_transform)) = cameras.iter().next() else {
+        return;
+    };
+
+    for (entity, mut popup, mut style, mut text) in &mut popups {
+        if popup.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // Follow the world position, so the popup stays put as the camera moves.
+        let Some(screen) = camera.world_to_viewport(camera_transform, popup.position.extend(0.))
+        else {
+            continue;
+        };
+
+        let t = popup.timer.fraction();
+        style.left = Val::Px(screen.x);
+        style.top = Val::Px(screen.y - SCORE_POPUP_RISE * t);
+        for section in &mut text.sections {
+            section.style.color.set_alpha(1. - t);
+        }
+    }
+}
+
+const HIGHLIGHT_PULSE: f32 = 0.15;
+const HIGHLIGHT_PULSE_SPEED: f32 = 6.;
+
+fn system_highlight_grabbable(
+    mut highlighted: Local<Option<Entity>>,
+    time: Res<Time>,
+    active: Query<(Entity, &SimPosition, &Grip), (With<CanHold>, With<Active>)>,
+    candidates: GrabCandidates,
+    mut transforms: Query<&mut Transform>,
+) {
+    let candidate = active
+        .get_single()
+        .ok()
+        .and_then(|(entity, position, grip)| candidates.candidate(entity, position, grip));
+
+    if *highlighted != candidate {
+        if let Some(mut transform) = highlighted.and_then(|e| transforms.get_mut(e).ok()) {
+            transform.scale = Vec3::ONE;
+        }
+        *highlighted = candidate;
+    }
+
+    if let Some(mut transform) = candidate.and_then(|e| transforms.get_mut(e).ok()) {
+        let pulse = (time.elapsed_seconds() * HIGHLIGHT_PULSE_SPEED).sin().abs();
+        transform.scale = Vec3::splat(1. + HIGHLIGHT_PULSE * pulse);
+    }
+}
+
+/// Every this many combo handovers, the after-image palette skips one more color.
+const COMBO_PALETTE_STEP: u32 = 3;
+
+#[derive(Resource, Default)]
+struct Combo {
+    count: u32,
+}
+
+#[derive(Component)]
+struct ComboText;
+
+fn system_update_combo(mut combo: ResMut<Combo>, mut events: EventReader<GameEvent>) {
+    for event in events.read() {
+        match event {
+            GameEvent::HandOver { .. } => combo.count += 1,
+            GameEvent::Drop | GameEvent::HazardReset => combo.count = 0,
+            _ => {}
+        }
+    }
+}
+
+fn system_setup_combo_text(mut commands: Commands) {
+    commands.spawn((
+        ComboText,
+        StateScoped(AppState::InGame),
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: 32.,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(16.),
+                left: Val::Px(16.),
+                ..default()
+            },
+            ..default()
+        },
+    ));
+}
+
+fn system_show_combo(combo: Res<Combo>, mut query: Query<&mut Text, With<ComboText>>) {
+    if !combo.is_changed() {
+        return;
+    }
+
+    for mut text in &mut query {
+        text.sections[0].value = if combo.count > 0 {
+            format!("COMBO x{}", combo.count)
+        } else {
+            String::new()
+        };
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct RunTimer(Stopwatch);
+
+impl RunTimer {
+    pub fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+#[derive(Component)]
+struct RunTimerText;
+
+fn format_run_time(elapsed: Duration) -> String {
+    let millis = elapsed.as_millis();
+    format!(
+        "{:02}:{:02}.{:03}",
+        millis / 60_000,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+fn system_update_run_timer(mut run_timer: ResMut<RunTimer>, time: Res<Time>) {
+    run_timer.0.tick(time.delta());
+}
+
+fn system_setup_run_timer_text(mut commands: Commands) {
+    commands.spawn((
+        RunTimerText,
+        StateScoped(AppState::InGame),
+        TextBundle {
+            text: Text::from_section(
+                format_run_time(Duration::ZERO),
+                TextStyle {
+                    font_size: 32.,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(16.),
+                right: Val::Px(16.),
+                ..default()
+            },
+            ..default()
+        },
+    ));
+}
+
+fn system_show_run_timer(
+    run_timer: Res<RunTimer>,
+    mut query: Query<&mut Text, With<RunTimerText>>,
+) {
+    if !run_timer.is_changed() {
+        return;
+    }
+
+    for mut text in &mut query {
+        text.sections[0].value = format_run_time(run_timer.0.elapsed());
+    }
+}
+
+/// World units shown as one meter on the finish distance HUD.
+const DISTANCE_UNIT: f32 = 128.;
+
+#[derive(Component)]
+struct FinishDistanceText;
+
+fn system_setup_finish_distance_text(mut commands: Commands) {
+    commands.spawn((
+        FinishDistanceText,
+        StateScoped(AppState::InGame),
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: 32.,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(56.),
+                right: Val::Px(16.),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+    ));
+}
+
+fn system_show_finish_distance(
+    active: Query<&GlobalTransform, (With<Hand>, With<Active>)>,
+    finish: Query<&GlobalTransform, With<Finish>>,
+    mut query: Query<(&mut Text, &mut Visibility), With<FinishDistanceText>>,
+) {
+    let distance = match (active.get_single(), finish.get_single()) {
+        (Ok(hand), Ok(finish)) => Some(hand.translation().xy().distance(finish.translation().xy())),
+        _ => None,
+    };
+
+    for (mut text, mut visibility) in &mut query {
+        let Some(distance) = distance else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+
+        visibility.set_if_neq(Visibility::Inherited);
+        let value = format!("FINISH {:.0}m", distance / DISTANCE_UNIT);
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+/// Size of the minimap panel in logical pixels.
+pub const MINIMAP_SIZE: Vec2 = Vec2::new(200., 120.);
+/// Distance from the minimap to the bottom left corner of the window.
+pub const MINIMAP_MARGIN: f32 = 16.;
+/// Empty space kept around the level inside the minimap.
+const MINIMAP_PADDING: f32 = 12.;
+const MINIMAP_DOT_SIZE: f32 = 8.;
+
+#[derive(Component)]
+struct Minimap;
+
+/// A dot on the minimap standing in for a cycle, the finish or the baton.
+#[derive(Component)]
+struct MinimapDot(Entity);
+
+fn system_setup_minimap(mut commands: Commands) {
+    commands.spawn((
+        Minimap,
+        StateScoped(AppState::InGame),
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(MINIMAP_MARGIN),
+                left: Val::Px(MINIMAP_MARGIN),
+                width: Val::Px(MINIMAP_SIZE.x),
+                height: Val::Px(MINIMAP_SIZE.y),
+                ..default()
+            },
+            background_color: Color::srgba(0., 0., 0., 0.4).into(),
+            ..default()
+        },
+    ));
+}
+
+fn system_update_minimap(
+    mut commands: Commands,
+    minimap: Query<Entity, With<Minimap>>,
+    mut dots: Query<(Entity, &MinimapDot, &mut Style, &mut BackgroundColor)>,
+    tracked: Query<
+        (Entity, &GlobalTransform, Has<Finish>, Has<Item>),
+        Or<(With<Cycle>, With<Finish>, With<Item>)>,
+    >,
+    active: Query<&Parent, (With<Hand>, With<Active>)>,
+) {
+    let Ok(minimap) = minimap.get_single() else {
+        return;
+    };
+
+    let mut dotted = HashSet::new();
+    for (entity, MinimapDot(target), ..) in &dots {
+        if tracked.contains(*target) {
+            dotted.insert(*target);
+        } else {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    for (entity, _, _, is_baton) in &tracked {
+        if dotted.contains(&entity) {
+            continue;
+        }
+
+        let dot = commands
+            .spawn((
+                MinimapDot(entity),
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(MINIMAP_DOT_SIZE),
+                        height: Val::Px(MINIMAP_DOT_SIZE),
+                        ..default()
+                    },
+                    border_radius: BorderRadius::MAX,
+                    z_index: ZIndex::Local(is_baton as i32),
+                    ..default()
+                },
+            ))
+            .id();
+        commands.entity(minimap).add_child(dot);
+    }
+
+    let Some((min, max)) = tracked
+        .iter()
+        .map(|(_, transform, ..)| transform.translation().xy())
+        .fold(None, |bounds: Option<(Vec2, Vec2)>, position| {
+            Some(bounds.map_or((position, position), |(min, max)| {
+                (min.min(position), max.max(position))
+            }))
+        })
+    else {
+        return;
+    };
+
+    let inner = MINIMAP_SIZE - Vec2::splat(MINIMAP_PADDING * 2.);
+    let extent = (max - min).max(Vec2::ONE);
+    let scale = (inner.x / extent.x).min(inner.y / extent.y);
+    let offset = (MINIMAP_SIZE - extent * scale) / 2.;
+    let active_cycle = active.get_single().ok().map(Parent::get);
+
+    for (_, MinimapDot(target), mut style, mut background) in &mut dots {
+        let Ok((entity, transform, is_finish, is_baton)) = tracked.get(*target) else {
+            continue;
+        };
+
+        let position = (transform.translation().xy() - min) * scale + offset;
+        style.left = Val::Px(position.x - MINIMAP_DOT_SIZE / 2.);
+        style.top = Val::Px(MINIMAP_SIZE.y - position.y - MINIMAP_DOT_SIZE / 2.);
+
+        let color = if is_baton {
+            Color::Srgba(ORANGE_100)
+        } else if is_finish {
+            Color::Srgba(GREEN_100)
+        } else if active_cycle == Some(entity) {
+            Color::Srgba(YELLOW_100)
+        } else {
+            Color::Srgba(GRAY)
+        };
+        background.set_if_neq(color.into());
+    }
+}
+
+/// Positions of the active hand sampled over one run of a level.
+#[derive(Clone, Default)]
+struct GhostRun {
+    samples: Vec<(f32, Vec2)>,
+}
+
+impl GhostRun {
+    fn duration(&self) -> f32 {
+        self.samples.last().map(|(time, _)| *time).unwrap_or(0.)
+    }
+
+    fn position_at(&self, time: f32) -> Option<Vec2> {
+        let next = self.samples.partition_point(|(t, _)| *t < time);
+        let (to_time, to) = *self.samples.get(next)?;
+        let Some((from_time, from)) = next.checked_sub(1).map(|i| self.samples[i]) else {
+            return Some(to);
+        };
+
+        let t = (time - from_time) / (to_time - from_time).max(f32::EPSILON);
+        Some(from.lerp(to, t))
+    }
+}
+
+/// The best run per level seed, level index and difficulty, plus the run being recorded.
+#[derive(Resource, Default)]
+struct GhostReplay {
+    best: HashMap<(u64, usize, Difficulty), GhostRun>,
+    current: GhostRun,
+    elapsed: f32,
+}
+
+#[derive(Component)]
+struct Ghost;
+
+fn system_start_ghost(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut ghost: ResMut<GhostReplay>,
+    seed: Res<LevelSeed>,
+    current_level: Res<CurrentLevel>,
+    difficulty: Res<RunDifficulty>,
+    existing: Query<Entity, With<Ghost>>,
+) {
+    for entity in existing.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    ghost.current = GhostRun::default();
+    ghost.elapsed = 0.;
+
+    let Some(start) = ghost
+        .best
+        .get(&(seed.0, current_level.0, difficulty.0))
+        .and_then(|best| best.position_at(0.))
+    else {
+        return;
+    };
+
+    commands.spawn((
+        Ghost,
+        LevelEntity,
+        SpriteBundle {
+            texture: assets.hand_open.clone(),
+            sprite: Sprite {
+                color: TINT_GHOST,
+                custom_size: Some(Vec2::splat(64.0)),
+                ..default()
+            },
+            transform: Transform::from_translation(start.extend(1.5)),
+            ..default()
+        },
+        RenderLayers::layer(LAYER_ACTIVE),
+    ));
+}
+
+fn system_record_ghost(
+    mut ghost: ResMut<GhostReplay>,
+    active: Query<&GlobalTransform, (With<Hand>, With<Active>)>,
+    time: Res<Time>,
+) {
+    ghost.elapsed += time.delta_seconds();
+
+    let Ok(transform) = active.get_single() else {
+        return;
+    };
+
+    let sample = (ghost.elapsed, transform.translation().truncate());
+    ghost.current.samples.push(sample);
+}
+
+fn system_play_ghost(
+    ghost: Res<GhostReplay>,
+    seed: Res<LevelSeed>,
+    current_level: Res<CurrentLevel>,
+    difficulty: Res<RunDifficulty>,
+    mut query: Query<(&mut Transform, &mut Visibility), With<Ghost>>,
+) {
+    let best = ghost.best.get(&(seed.0, current_level.0, difficulty.0));
+
+    for (mut transform, mut visibility) in query.iter_mut() {
+        match best.and_then(|best| best.position_at(ghost.elapsed)) {
+            Some(position) => {
+                transform.translation.x = position.x;
+                transform.translation.y = position.y;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+fn system_store_ghost(
+    mut ghost: ResMut<GhostReplay>,
+    seed: Res<LevelSeed>,
+    current_level: Res<CurrentLevel>,
+    difficulty: Res<RunDifficulty>,
+) {
+    let run = std::mem::take(&mut ghost.current);
+    let faster = ghost
+        .best
+        .get(&(seed.0, current_level.0, difficulty.0))
+        .is_none_or(|best| run.duration() < best.duration());
+
+    if faster && !run.samples.is_empty() {
+        ghost
+            .best
+            .insert((seed.0, current_level.0, difficulty.0), run);
+    }
+}
+
+/// Seconds a dropped baton may lie around before the run is lost.
+const DROP_TIMEOUT: f32 = 5.;
+
+#[derive(Resource, Default)]
+struct DropTimer(Option<Timer>);
+
+fn system_drop_timer(
+    mut drop_timer: ResMut<DropTimer>,
+    difficulty: Res<RunDifficulty>,
+    mut events: EventReader<GameEvent>,
+    time: Res<Time>,
+    mut state: ResMut<NextState<Game>>,
+) {
+    for event in events.read() {
+        match event {
+            GameEvent::Drop => {
+                drop_timer.0 = Some(Timer::from_seconds(
+                    difficulty.0.drop_timeout(),
+                    TimerMode::Once,
+                ));
+            }
+            GameEvent::Grab => {
+                drop_timer.0 = None;
+            }
+            _ => {}
+        }
+    }
+
+    let Some(timer) = &mut drop_timer.0 else {
+        return;
+    };
+
+    if timer.tick(time.delta()).just_finished() {
+        drop_timer.0 = None;
+        state.set(Game::Failed);
+    }
+}
+
+fn system_show_failed_text(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    enabled: Res<CheckpointsEnabled>,
+    checkpoint: Res<CheckpointState>,
+    bindings: Res<KeyBindings>,
+    hands: Query<(), With<Hand>>,
+) {
+    let can_continue = enabled.0 && checkpoint.hand.is_some_and(|hand| hands.contains(hand));
+
+    spawn_banner(&mut commands, &assets.font, "BATON LOST").with_children(|parent| {
+        let mut prompts = vec![(Action::Restart, "RESTART")];
+        if can_continue {
+            prompts.push((Action::Continue, "CONTINUE FROM CHECKPOINT"));
+        }
+
+        // Unbound actions can't be pressed, so they aren't offered.
+        let lines = prompts.into_iter().filter_map(|(action, label)| {
+            let key = bindings.key(action)?;
+            Some(format!("PRESS {} TO {label}", key_label(key)))
+        });
+
+        for line in lines {
+            parent.spawn(TextBundle {
+                text: Text::from_section(
+                    line,
+                    TextStyle {
+                        font_size: 32.,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                ..default()
+            });
+        }
+    });
+}
+
+/// Whether a lost baton can be given back to the last hand that received it.
+#[derive(Resource)]
+pub struct CheckpointsEnabled(pub bool);
+
+impl Default for CheckpointsEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// The hand that most recently received the baton in a handover.
+#[derive(Resource, Default)]
+struct CheckpointState {
+    hand: Option<Entity>,
+}
+
+fn system_capture_checkpoint(
+    mut events: EventReader<GameEvent>,
+    mut checkpoint: ResMut<CheckpointState>,
+    active: Query<Entity, (With<Hand>, With<Active>)>,
+) {
+    for event in events.read() {
+        if let GameEvent::HandOver { .. } = event {
+            if let Ok(hand) = active.get_single() {
+                checkpoint.hand = Some(hand);
+            }
+        }
+    }
+}
+
+fn system_continue_from_checkpoint(
+    mut commands: Commands,
+    action_input: Res<ActionInput>,
+    enabled: Res<CheckpointsEnabled>,
+    checkpoint: Res<CheckpointState>,
+    mut drop_timer: ResMut<DropTimer>,
+    mut state: ResMut<NextState<Game>>,
+    hands: Query<(), With<Hand>>,
+    active: Query<Entity, With<Active>>,
+    items: Query<Entity, With<Item>>,
+    banners: Query<Entity, With<Banner>>,
+) {
+    if !enabled.0 || !action_input.just_pressed(Action::Continue) {
+        return;
+    }
+
+    let Some(hand) = checkpoint.hand.filter(|hand| hands.contains(*hand)) else {
+        return;
+    };
+
+    let Some(item) = items.iter().next() else {
+        return;
+    };
+
+    for entity in &active {
+        commands
+            .entity(entity)
+            .remove::<Active>()
+            .insert(Grip::Empty);
+    }
+
+    commands.entity(hand).insert((Active, Grip::Holding(item)));
+    commands.entity(item).set_parent_in_place(hand);
+
+    for entity in &banners {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    drop_timer.0 = None;
+    state.set(Game::Playing);
+}
+
+#[derive(Resource)]
+pub struct Volume {
+    pub master: f32,
+    pub sfx: f32,
+    pub music: f32,
+}
+
+impl Default for Volume {
+    fn default() -> Self {
+        Self {
+            master: 1.,
+            sfx: 1.,
+            music: 1.,
+        }
+    }
+}
+
+impl Volume {
+    fn sfx(&self) -> f32 {
+        self.master * self.sfx
+    }
+
+    fn music(&self) -> f32 {
+        self.master * self.music
+    }
+}
+
+const SFX_PITCH_MIN: f32 = 0.8;
+const SFX_PITCH_MAX: f32 = 1.4;
+
+/// Faster cycles play their sounds at a higher pitch.
+fn sfx_pitch(speed: f32) -> f32 {
+    (SFX_PITCH_MIN + speed.abs() * 0.12).clamp(SFX_PITCH_MIN, SFX_PITCH_MAX)
+}
+
+/// The grab sound raised into a chime for pickups.
+const PICKUP_SFX_PITCH: f32 = 1.8;
+
+fn system_play_sfx(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    volume: Res<Volume>,
+    active: Query<&Speed, With<Active>>,
+    mut events: EventReader<GameEvent>,
+) {
+    let hand_over = &assets.hand_over_sfx;
+    let select = &assets.select_sfx;
+    let select_miss = &assets.select_miss_sfx;
+    let settings = PlaybackSettings::ONCE.with_volume(AudioVolume::new(volume.sfx()));
+
+    for event in events.read() {
+        match event {
+            GameEvent::Drop => {
+                commands.spawn(AudioBundle {
+                    source: select_miss.clone(),
+                    settings,
+                });
+            }
+            GameEvent::HandOver { quality } => {
+                let quality_pitch = match quality {
+                    Quality::Perfect => 1.25,
+                    Quality::Good => 1.1,
+                    Quality::Ok => 1.,
+                };
+                let speed = active.get_single().map(|Speed(speed)| *speed).unwrap_or(0.);
+                let pitch = (quality_pitch * sfx_pitch(speed)).clamp(SFX_PITCH_MIN, SFX_PITCH_MAX);
+                commands.spawn(AudioBundle {
+                    source: hand_over.clone(),
+                    settings: settings.with_speed(pitch),
+                });
+            }
+            GameEvent::Grab => {
+                commands.spawn(AudioBundle {
+                    source: select.clone(),
+                    settings,
+                });
+            }
+            GameEvent::GrabEmpty | GameEvent::HazardReset => {
+                commands.spawn(AudioBundle {
+                    source: select_miss.clone(),
+                    settings,
+                });
+            }
+            GameEvent::Pickup { .. } => {
+                commands.spawn(AudioBundle {
+                    source: select.clone(),
+                    settings: settings.with_speed(PICKUP_SFX_PITCH),
+                });
+            }
+        }
+    }
+}
+
+/// Strong rumble felt when the baton is dropped.
+const RUMBLE_DROP_INTENSITY: f32 = 0.8;
+const RUMBLE_DROP_DURATION: Duration = Duration::from_millis(250);
+/// Light tick felt on every handover.
+const RUMBLE_HAND_OVER_INTENSITY: f32 = 0.3;
+const RUMBLE_HAND_OVER_DURATION: Duration = Duration::from_millis(60);
+
+fn system_rumble(
+    gamepads: Res<Gamepads>,
+    mut events: EventReader<GameEvent>,
+    mut rumble: EventWriter<GamepadRumbleRequest>,
+) {
+    for event in events.read() {
+        let (intensity, duration) = match event {
+            GameEvent::Drop => (
+                GamepadRumbleIntensity::strong_motor(RUMBLE_DROP_INTENSITY),
+                RUMBLE_DROP_DURATION,
+            ),
+            GameEvent::HandOver { .. } => (
+                GamepadRumbleIntensity::weak_motor(RUMBLE_HAND_OVER_INTENSITY),
+                RUMBLE_HAND_OVER_DURATION,
+            ),
+            _ => continue,
+        };
+
+        for gamepad in gamepads.iter() {
+            rumble.send(GamepadRumbleRequest::Add {
+                gamepad,
+                intensity,
+                duration,
+            });
+        }
+    }
+}
+
+fn system_apply_volume(volume: Res<Volume>, sinks: Query<(&AudioSink, Has<Music>)>) {
+    if !volume.is_changed() {
+        return;
+    }
+
+    for (sink, is_music) in &sinks {
+        if is_music {
+            sink.set_volume(volume.music());
+        } else {
+            sink.set_volume(volume.sfx());
+        }
+    }
+}
+
+/// Generic over the sink so it can be run against a stand-in without an audio device.
+fn system_clean_up_sfx<S: Component + AudioSinkPlayback>(
+    mut commands: Commands,
+    sfxs: Query<(Entity, &S)>,
+) {
+    for (entity, sink) in &sfxs {
+        if sink.is_paused() || sink.empty() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn system_lerp_item_to_holding(
+    holders: Query<(&GlobalTransform, &Grip)>,
+    mut items: Query<(&mut Transform, &GlobalTransform, &Parent), With<Item>>,
+    parents: Query<&GlobalTransform>,
+) {
+    for (holder_transform, grip) in &holders {
+        let Grip::Holding(item) = grip else {
+            continue;
+        };
+
+        let Ok((mut transform, global_transform, parent)) = items.get_mut(*item) else {
+            continue;
+        };
+
+        let Ok(parent_transform) = parents.get(parent.get()) else {
+            continue;
+        };
+
+        // Lerp in world space so a fresh reparent can't make the item jump, then
+        // bring the result back into whatever the parent currently is.
+        let world = global_transform.translation();
+        let next = world.lerp(holder_transform.translation().xy().extend(world.z), 0.03);
+        let local = parent_transform.affine().inverse().transform_point3(next);
+
+        transform.translation.x = local.x;
+        transform.translation.y = local.y;
+    }
+}
+
+fn system_set_render_layer(
+    mut query: Query<(Entity, Option<&Parent>, &mut RenderLayers), With<Active>>,
+    mut others: Query<
+        (Entity, &mut RenderLayers),
+        (
+            Or<(With<Hand>, With<Item>, With<Cycle>, With<Finish>)>,
+            Without<Active>,
+        ),
+    >,
+    overlap: Res<Overlap>,
+) {
+    let active = query.get_single_mut().ok();
+    let (active_entity, active_parent) = match active {
+        Some((entity, parent, mut render_layers)) => {
+            render_layers.set_if_neq(RenderLayers::layer(LAYER_ACTIVE));
+            (Some(entity), parent.map(Parent::get))
+        }
+        None => (None, None),
+    };
+
+    // Only write when the layer differs, so unchanged entities aren't marked as changed.
+    for (other, mut render_layers) in &mut others {
+        let highlighted = active_parent == Some(other)
+            || active_entity.is_some_and(|entity| overlap.between(entity, other));
+        let layer = if highlighted {
+            LAYER_ACTIVE
+        } else {
+            LAYER_INACTIVE
+        };
+        render_layers.set_if_neq(RenderLayers::layer(layer));
+    }
+}
+
+fn system_tint_layers(
+    mut query: Query<
+        (&mut Sprite, &RenderLayers, Option<&CycleColor>),
+        (
+            Without<Ghost>,
+            Without<Hazard>,
+            Without<Pickup>,
+            Without<Background>,
+        ),
+    >,
+    scheme: Res<ColorScheme>,
+    palette: Res<AfterImagePalette>,
+) {
+    for (mut sprite, render_layers, cycle_color) in &mut query {
+        let is_active = render_layers == &RenderLayers::layer(LAYER_ACTIVE);
+        if !is_active && render_layers != &RenderLayers::layer(LAYER_INACTIVE) {
+            continue;
+        }
+
+        let tint = match (cycle_color, is_active) {
+            (Some(CycleColor(index)), true) => {
+                palette.0[index % palette.0.len()].lighter(CYCLE_ACTIVE_LIGHTEN)
+            }
+            (Some(CycleColor(index)), false) => {
+                palette.0[index % palette.0.len()].darker(CYCLE_INACTIVE_DARKEN)
+            }
+            (None, true) => scheme.active(),
+            (None, false) => scheme.inactive(),
+        };
+        if sprite.color != tint {
+            sprite.color = tint;
+        }
+    }
+}
+
+fn system_cycle_hand(
+    mut query: Query<(&Children, &Radius), With<Cycle>>,
+    mut hands: Query<(&mut Transform, &Progress, &PreviousProgress), With<Hand>>,
+    fixed: Res<Time<Fixed>>,
+) {
+    let alpha = fixed.overstep_fraction();
+
+    for (children, radius) in query.iter_mut() {
+        for child in children.iter() {
+            let Ok((mut hand, Progress(progress), PreviousProgress(previous))) =
+                hands.get_mut(*child)
+            else {
+                continue;
+            };
+
+            // Progress wraps between 1 and 0, interpolate across the seam either way.
+            let progress = match progress - previous {
+                step if step < -0.5 => progress + 1.,
+                step if step > 0.5 => progress - 1.,
+                _ => *progress,
+            };
+            let angle = Progress(previous + (progress - previous) * alpha).angle();
+            let offset = Vec2::new(angle.cos(), angle.sin()) * radius.0;
+            hand.translation.x = offset.x;
+            hand.translation.y = offset.y;
+        }
+    }
+}
+
+fn system_draw_progress_ring(
+    active: Query<(&Progress, &Parent), (With<Hand>, With<Active>)>,
+    cycles: Query<(&GlobalTransform, &Radius), With<Cycle>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok((progress, parent)) = active.get_single() else {
+        return;
+    };
+
+    let Ok((transform, radius)) = cycles.get(parent.get()) else {
+        return;
+    };
+
+    // Gizmo arcs are measured clockwise from the y axis, progress counterclockwise from x.
+    let sweep = progress.angle();
+    gizmos.arc_2d(
+        transform.translation().xy(),
+        PI / 2. - sweep / 2.,
+        sweep,
+        radius.0,
+        Color::WHITE,
+    );
+}
+
+/// Distance in world units kept between the finish arrow and the edge of the view.
+const FINISH_ARROW_MARGIN: f32 = 48.;
+const FINISH_ARROW_LENGTH: f32 = 64.;
+
+fn system_finish_arrow(
+    camera: Query<(&GlobalTransform, &OrthographicProjection), With<Camera>>,
+    active: Query<&GlobalTransform, (With<Hand>, With<Active>)>,
+    finish: Query<&GlobalTransform, With<Finish>>,
+    mut gizmos: Gizmos,
+) {
+    let (Some((camera, projection)), Ok(hand), Ok(finish)) = (
+        camera.iter().next(),
+        active.get_single(),
+        finish.get_single(),
+    ) else {
+        return;
+    };
+
+    let center = camera.translation().xy();
+    let target = finish.translation().xy();
+    let half_size = projection.area.half_size();
+    let offset = target - center;
+    if offset.x.abs() <= half_size.x && offset.y.abs() <= half_size.y {
+        return;
+    }
+
+    // Walk from the center of the view toward the finish until reaching the inset edge.
+    let inset = (half_size - Vec2::splat(FINISH_ARROW_MARGIN)).max(Vec2::ZERO);
+    let t = (inset.x / offset.x.abs()).min(inset.y / offset.y.abs());
+    let tip = center + offset * t;
+
+    let Ok(direction) = Dir2::new(target - hand.translation().xy()) else {
+        return;
+    };
+    gizmos.arrow_2d(tip - direction * FINISH_ARROW_LENGTH, tip, Color::WHITE);
+}
+
+fn system_move_cycles(time: Res<Time>, mut query: Query<(&mut Transform, &mut CycleMotion)>) {
+    for (mut transform, mut motion) in &mut query {
+        motion.elapsed += time.delta_seconds();
+        let t = 0.5 - 0.5 * (motion.elapsed * 2. * PI / motion.period).cos();
+        let position = motion.from.lerp(motion.to, t);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+#[derive(Resource)]
+struct CameraConfig {
+    follow_lerp: f32,
+    /// Jump straight to the focus instead of following it.
+    snap: bool,
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        Self {
+            follow_lerp: 0.05,
+            snap: false,
+        }
+    }
+}
+
+/// World units the camera looks ahead per unit of the active hand's speed.
+const CAMERA_LEAD_PER_SPEED: f32 = 24.;
+const CAMERA_LEAD_MAX: f32 = 96.;
+
+/// Offset added to [`CameraFocus`] so the camera looks ahead of the active hand's motion.
+#[derive(Resource, Default)]
+struct CameraLead(Vec2);
+
+fn system_update_camera_lead(
+    active: Query<(&Progress, &Speed, &Direction), (With<Hand>, With<Active>)>,
+    mut lead: ResMut<CameraLead>,
+) {
+    lead.0 = match active.get_single() {
+        Ok((progress, Speed(speed), Direction(direction))) => {
+            // Progress runs counterclockwise, so the tangent is the radius rotated a quarter turn.
+            let angle = progress.angle();
+            let tangent = Vec2::new(-angle.sin(), angle.cos()) * *direction;
+            tangent * (speed * CAMERA_LEAD_PER_SPEED).min(CAMERA_LEAD_MAX)
+        }
+        Err(_) => Vec2::ZERO,
+    };
+}
+
+/// Half extents of the area around the camera center within which focus changes are ignored.
+#[derive(Resource)]
+struct CameraDeadZone(Vec2);
+
+impl Default for CameraDeadZone {
+    fn default() -> Self {
+        Self(vec2(24., 16.))
+    }
+}
+
+/// The area covered by the current level's cycles and finish, if one is loaded.
+#[derive(Resource, Default)]
+struct LevelBounds(Option<Rect>);
+
+impl LevelBounds {
+    /// Moves `target` so a view of `half_size` around it stays inside the level,
+    /// centering on the level along any axis where the view is wider than it.
+    fn clamp(&self, target: Vec2, half_size: Vec2) -> Vec2 {
+        let Some(bounds) = self.0 else {
+            return target;
+        };
+
+        let min = bounds.min + half_size;
+        let max = bounds.max - half_size;
+        let center = bounds.center();
+        vec2(
+            if min.x > max.x {
+                center.x
+            } else {
+                target.x.clamp(min.x, max.x)
+            },
+            if min.y > max.y {
+                center.y
+            } else {
+                target.y.clamp(min.y, max.y)
+            },
+        )
+    }
+}
+
+fn system_update_level_bounds(
+    added: Query<(), Or<(Added<Cycle>, Added<Finish>)>>,
+    mut removed: RemovedComponents<Cycle>,
+    places: Query<&GlobalTransform, Or<(With<Cycle>, With<Finish>)>>,
+    mut bounds: ResMut<LevelBounds>,
+) {
+    if added.is_empty() && removed.read().count() == 0 {
+        return;
+    }
+
+    bounds.0 = places
+        .iter()
+        .map(|transform| {
+            Rect::from_center_half_size(transform.translation().xy(), Vec2::splat(RADIUS_CYCLE))
+        })
+        .reduce(|bounds, place| bounds.union(place));
+}
+
+fn system_lerp_camera_to_focus(
+    focus: Res<CameraFocus>,
+    config: Res<CameraConfig>,
+    lead: Res<CameraLead>,
+    bounds: Res<LevelBounds>,
+    dead_zone: Res<CameraDeadZone>,
+    mut query: Query<(&mut Transform, &OrthographicProjection), With<Camera>>,
+) {
+    for (mut transform, projection) in &mut query {
+        let center = transform.translation.xy();
+        let offset = bounds.clamp(focus.0 + lead.0, projection.area.half_size()) - center;
+        // Only follow by as much as the target has left the dead zone.
+        let target = (center + offset - offset.clamp(-dead_zone.0, dead_zone.0))
+            .extend(transform.translation.z);
+        transform.translation = if config.snap {
+            target
+        } else {
+            transform.translation.lerp(target, config.follow_lerp)
+        };
+    }
+}
+
+const CAMERA_SHAKE_MAGNITUDE: f32 = 24.;
+const CAMERA_SHAKE_DROP_TRAUMA: f32 = 0.6;
+/// Trauma lost per second.
+const CAMERA_SHAKE_DECAY: f32 = 1.5;
+
+/// Shake is kept as an offset on top of the followed position. It is taken off
+/// before following the focus and put back on afterwards so the two don't fight.
+#[derive(Resource, Default)]
+struct CameraShake {
+    trauma: f32,
+    offset: Vec2,
+}
+
+fn system_bump_camera_shake(mut shake: ResMut<CameraShake>, mut events: EventReader<GameEvent>) {
+    for event in events.read() {
+        if let GameEvent::Drop | GameEvent::HazardReset = event {
+            shake.trauma = (shake.trauma + CAMERA_SHAKE_DROP_TRAUMA).min(1.);
+        }
+    }
+}
+
+fn system_remove_camera_shake(
+    shake: Res<CameraShake>,
+    mut query: Query<&mut Transform, With<Camera>>,
+) {
+    for mut transform in &mut query {
+        transform.translation -= shake.offset.extend(0.);
+    }
+}
+
+fn system_apply_camera_shake(
+    time: Res<Time>,
+    mut shake: ResMut<CameraShake>,
+    mut query: Query<&mut Transform, With<Camera>>,
+) {
+    shake.trauma = (shake.trauma - CAMERA_SHAKE_DECAY * time.delta_seconds()).max(0.);
+
+    let t = time.elapsed_seconds();
+    let noise = vec2((t * 53.).sin(), (t * 71.).cos());
+    shake.offset = noise * shake.trauma * shake.trauma * CAMERA_SHAKE_MAGNITUDE;
+
+    for mut transform in &mut query {
+        transform.translation += shake.offset.extend(0.);
+    }
+}
+
+const ZOOM_MIN: f32 = 0.75;
+const ZOOM_MAX: f32 = 1.;
+/// Distance to the finish from which the camera stays fully zoomed out.
+const ZOOM_DISTANCE: f32 = 1536.;
+
+#[derive(Resource)]
+struct CameraZoom {
+    target: f32,
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self { target: ZOOM_MAX }
+    }
+}
+
+fn system_update_camera_zoom(
+    mut zoom: ResMut<CameraZoom>,
+    active: Query<&GlobalTransform, With<Active>>,
+    finishes: Query<&GlobalTransform, With<Finish>>,
+) {
+    let (Ok(active), Some(finish)) = (active.get_single(), finishes.iter().next()) else {
+        return;
+    };
+
+    let distance = active
+        .translation()
+        .xy()
+        .distance(finish.translation().xy());
+    zoom.target = ZOOM_MIN.lerp(ZOOM_MAX, (distance / ZOOM_DISTANCE).clamp(0., 1.));
+}
+
+fn system_lerp_camera_zoom(
+    zoom: Res<CameraZoom>,
+    mut query: Query<&mut OrthographicProjection, With<Camera>>,
+) {
+    for mut projection in &mut query {
+        projection.scale = projection
+            .scale
+            .lerp(zoom.target, 0.05)
+            .clamp(ZOOM_MIN, ZOOM_MAX);
+    }
+}
+
+fn on_add_active(
+    trigger: Trigger<OnAdd, Active>,
+    transforms: Query<&GlobalTransform>,
+    parents: Query<&Parent>,
+    cycles: Query<(), With<Cycle>>,
+    mut focus: ResMut<CameraFocus>,
+) {
+    let entity = trigger.entity();
+    // Focus on the cycle the entity travels around, however deep below it the entity sits.
+    let target = parents
+        .iter_ancestors(entity)
+        .find(|ancestor| cycles.contains(*ancestor))
+        .unwrap_or(entity);
+
+    if let Ok(transform) = transforms.get(target) {
+        focus.0 = transform.translation().xy();
+    }
+}
+
+fn on_insert_grip(trigger: Trigger<OnInsert, Grip>, mut query: Query<(&Grip, &mut HandAnimation)>) {
+    if let Ok((grip, mut animation)) = query.get_mut(trigger.entity()) {
+        animation.closing = *grip != Grip::Empty;
+    }
+}
+
+fn system_animate_hands(
+    time: Res<Time>,
+    mut query: Query<(
+        &mut HandAnimation,
+        &mut AnimationTimer,
+        &HandSprites,
+        &mut TextureAtlas,
+    )>,
+) {
+    for (mut animation, mut timer, hand_sprites, mut texture_atlas) in &mut query {
+        let target = if animation.closing {
+            hand_sprites.frames.len() - 1
+        } else {
+            0
+        };
+        if animation.frame == target {
+            timer.reset();
+            continue;
+        }
+
+        if !timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        if animation.frame < target {
+            animation.frame += 1;
+        } else {
+            animation.frame -= 1;
+        }
+        texture_atlas.index = hand_sprites.frames[animation.frame];
+    }
+}
+
+/// Present once the finish of the current level has been reached.
+#[derive(Resource)]
+struct FinishShown {
+    /// Whether the level was finished faster than ever before.
+    new_best: bool,
+}
+
+fn on_finish(
+    trigger: Trigger<OnAdd, Active>,
+    mut commands: Commands,
+    mut state: ResMut<NextState<Game>>,
+    query: Query<&Finish>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+    run_timer: Res<RunTimer>,
+    mode: Res<GameMode>,
+    finish_shown: Option<Res<FinishShown>>,
+    difficulty: Res<RunDifficulty>,
+    mut high_scores: ResMut<HighScores>,
+    mut completed: EventWriter<LevelCompleted>,
+) {
+    if *mode == GameMode::Endless {
+        return;
+    }
+
+    if query.get(trigger.entity()).is_err() || finish_shown.is_some() {
+        return;
+    }
+
+    let new_best = high_scores.submit(current_level.0, difficulty.0, run_timer.elapsed());
+    commands.insert_resource(FinishShown { new_best });
+    completed.send(LevelCompleted {
+        level: current_level.0,
+        time: run_timer.elapsed(),
+    });
+
+    if current_level.0 + 1 < levels.0.len() {
+        state.set(Game::LevelComplete);
+    } else {
+        state.set(Game::Finished);
+    }
+}
+
+fn system_fade_out_everything(mut query: Query<&mut Sprite, (Without<Item>, Without<Background>)>) {
+    for mut sprite in &mut query {
+        let next_alpha = sprite.color.alpha().lerp(0., 0.1);
+        sprite.color.set_alpha(next_alpha);
+    }
+}
+
+fn system_magnify_baton(mut query: Query<(&mut Sprite, &mut ColliderScale), With<Item>>) {
+    for (mut sprite, mut collider_scale) in &mut query {
+        if let Some(previous_size) = sprite.custom_size {
+            let next_size = previous_size.lerp(vec2(256., 256.), 0.1);
+            sprite.custom_size = Some(next_size);
+            collider_scale.0 = next_size.x / BATON_SIZE;
+        }
+    }
+}
+
+#[derive(Component)]
+struct Banner;
+
+fn spawn_banner<'a>(
+    commands: &'a mut Commands,
+    font: &Handle<Font>,
+    text: impl Into<String>,
+) -> EntityCommands<'a> {
+    let mut banner = commands.spawn((
+        Banner,
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(10.),
+                left: Val::Px(0.),
+                right: Val::Px(0.),
+                bottom: Val::Px(0.),
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Start,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        },
+    ));
+
+    banner.with_children(|parent| {
+        parent.spawn(TextBundle {
+            text: Text::from_section(
+                text,
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 64.,
+                    color: Color::WHITE,
+                },
+            ),
+            ..default()
+        });
+    });
+
+    banner
+}
+
+#[derive(Component)]
+struct FinishText;
+
+/// Messages shown on the finish screen, swappable per theme or language.
+#[derive(Resource)]
+pub struct Strings {
+    pub finish: String,
+    pub new_best: String,
+}
+
+impl Default for Strings {
+    fn default() -> Self {
+        Self {
+            finish: "LIBERTY ACHIEVED".to_string(),
+            new_best: "NEW BEST".to_string(),
+        }
+    }
+}
+
+/// Seconds the finish text takes to fade in.
+const FINISH_FADE_DURATION: f32 = 1.;
+/// Scale the finish text grows from while fading in.
+const FINISH_FADE_SCALE: f32 = 0.8;
+
+#[derive(Component)]
+struct FinishFade(Timer);
+
+/// Tallies of what happened during the current run.
+#[derive(Resource, Default)]
+struct RunStats {
+    handovers: u32,
+    drops: u32,
+    max_combo: u32,
+}
+
+impl RunStats {
+    /// Label and value for each row of the summary screen.
+    fn rows(&self, elapsed: Duration, score: u32) -> Vec<(&'static str, String)> {
+        vec![
+            ("TIME", format_run_time(elapsed)),
+            ("SCORE", score.to_string()),
+            ("HANDOVERS", self.handovers.to_string()),
+            ("DROPS", self.drops.to_string()),
+            ("MAX COMBO", self.max_combo.to_string()),
+        ]
+    }
+}
+
+fn system_update_run_stats(
+    mut stats: ResMut<RunStats>,
+    mut events: EventReader<GameEvent>,
+    combo: Res<Combo>,
+) {
+    for event in events.read() {
+        match event {
+            GameEvent::HandOver { .. } => stats.handovers += 1,
+            GameEvent::Drop | GameEvent::HazardReset => stats.drops += 1,
+            _ => {}
+        }
+    }
+
+    if combo.count > stats.max_combo {
+        stats.max_combo = combo.count;
+    }
+}
+
+fn system_show_finish_text(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    strings: Res<Strings>,
+    run_timer: Res<RunTimer>,
+    stats: Res<RunStats>,
+    score: Res<Score>,
+    finish_shown: Option<Res<FinishShown>>,
+    existing: Query<(), With<FinishText>>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+
+    let new_best = finish_shown.is_some_and(|shown| shown.new_best);
+    let style = TextStyle {
+        font: assets.font.clone(),
+        font_size: 32.,
+        color: Color::WHITE,
+    };
+
+    let mut banner = spawn_banner(&mut commands, &assets.font, strings.finish.clone());
+    banner.insert((
+        FinishText,
+        FinishFade(Timer::from_seconds(FINISH_FADE_DURATION, TimerMode::Once)),
+    ));
+    banner.with_children(|parent| {
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    display: Display::Grid,
+                    grid_template_columns: vec![GridTrack::auto(), GridTrack::auto()],
+                    column_gap: Val::Px(32.),
+                    row_gap: Val::Px(4.),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|parent| {
+                for (label, value) in stats.rows(run_timer.0.elapsed(), score.0) {
+                    parent.spawn(TextBundle::from_section(label, style.clone()));
+                    parent.spawn(TextBundle::from_section(value, style.clone()));
+                }
+            });
+
+        if new_best {
+            parent.spawn(TextBundle {
+                text: Text::from_section(
+                    strings.new_best.clone(),
+                    TextStyle {
+                        color: Color::Srgba(YELLOW_100),
+                        ..style
+                    },
+                ),
+                ..default()
+            });
+        }
+    });
+}
+
+fn system_fade_in_finish_text(
+    time: Res<Time>,
+    mut banners: Query<(Entity, &mut FinishFade, &mut Transform)>,
+    children: Query<&Children>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, mut fade, mut transform) in &mut banners {
+        if fade.0.finished() {
+            continue;
+        }
+
+        fade.0.tick(time.delta());
+        let t = fade.0.fraction();
+        let eased = 1. - (1. - t).powi(2);
+        transform.scale = Vec3::splat(FINISH_FADE_SCALE.lerp(1., eased));
+
+        for descendant in children.iter_descendants(entity) {
+            if let Ok(mut text) = texts.get_mut(descendant) {
+                for section in &mut text.sections {
+                    section.style.color.set_alpha(t);
+                }
+            }
+        }
+    }
+}
+
+const COUNTDOWN_STEPS: [&str; 4] = ["3", "2", "1", "GO"];
+/// Seconds each countdown step stays on screen.
+const COUNTDOWN_STEP: f32 = 0.75;
+
+#[derive(Resource)]
+struct CountdownTimer(Timer);
+
+impl Default for CountdownTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            COUNTDOWN_STEP * COUNTDOWN_STEPS.len() as f32,
+            TimerMode::Once,
+        ))
+    }
+}
+
+#[derive(Component)]
+struct CountdownText;
+
+fn system_start_countdown(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut timer: ResMut<CountdownTimer>,
+) {
+    timer.0.reset();
+    spawn_banner(&mut commands, &assets.font, COUNTDOWN_STEPS[0]).insert(CountdownText);
+}
+
+fn system_countdown(
+    time: Res<Time>,
+    mut timer: ResMut<CountdownTimer>,
+    mut state: ResMut<NextState<Game>>,
+    banners: Query<&Children, With<CountdownText>>,
+    mut texts: Query<&mut Text>,
+) {
+    if timer.0.tick(time.delta()).finished() {
+        state.set(Game::Playing);
+        return;
+    }
+
+    let step = (timer.0.elapsed_secs() / COUNTDOWN_STEP) as usize;
+    let label = COUNTDOWN_STEPS[step.min(COUNTDOWN_STEPS.len() - 1)];
+
+    for children in &banners {
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                if text.sections[0].value != label {
+                    text.sections[0].value = label.to_string();
+                }
+            }
+        }
+    }
+}
+
+fn system_end_countdown(mut commands: Commands, query: Query<Entity, With<CountdownText>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// What the player has to do to move past a tutorial step.
+#[derive(Clone, Copy)]
+enum TutorialGoal {
+    Grab,
+    HandOver,
+}
+
+impl TutorialGoal {
+    fn reached_by(self, event: &GameEvent) -> bool {
+        matches!(
+            (self, event),
+            (TutorialGoal::Grab, GameEvent::Grab)
+                | (TutorialGoal::HandOver, GameEvent::HandOver { .. })
+        )
+    }
+}
+
+struct TutorialStep {
+    /// Shown until the goal is reached, `{grab}` is replaced with the grab key.
+    prompt: &'static str,
+    goal: TutorialGoal,
+}
+
+const TUTORIAL_STEPS: &[TutorialStep] = &[
+    TutorialStep {
+        prompt: "PRESS {grab} TO GRAB",
+        goal: TutorialGoal::Grab,
+    },
+    TutorialStep {
+        prompt: "PASS TO THE NEXT HAND",
+        goal: TutorialGoal::HandOver,
+    },
+];
+
+#[derive(Resource, Default)]
+struct Tutorial {
+    step: usize,
+}
+
+const TUTORIAL_NAME: &str = "tutorial";
+
+/// Whether the tutorial has been played through, across sessions.
+#[derive(Resource)]
+struct TutorialDone {
+    done: bool,
+    storage: Box<dyn SaveStorage>,
+}
+
+impl Default for TutorialDone {
+    fn default() -> Self {
+        let storage = platform_storage(TUTORIAL_NAME);
+        let done = storage
+            .load()
+            .and_then(|contents| match ron::from_str(&contents) {
+                Ok(done) => Some(done),
+                Err(error) => {
+                    warn!("Could not read tutorial progress: {error}");
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self { done, storage }
+    }
+}
+
+impl TutorialDone {
+    /// Marks the tutorial as played through and saves that, the first time only.
+    fn complete(&mut self) {
+        if self.done {
+            return;
+        }
+
+        self.done = true;
+        match ron::to_string(&self.done) {
+            Ok(contents) => self.storage.save(&contents),
+            Err(error) => warn!("Could not serialize tutorial progress: {error}"),
+        }
+    }
+}
+
+#[derive(Component)]
+struct TutorialText;
+
+fn system_start_tutorial(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    difficulty: Res<Difficulty>,
+    mut tutorial: ResMut<Tutorial>,
+    level_entities: Query<Entity, (With<LevelEntity>, Without<Parent>)>,
+) {
+    for entity in &level_entities {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    tutorial.step = 0;
+    spawn_level(&mut commands, &assets, &LevelDef::tutorial(), *difficulty);
+    commands.spawn((
+        TutorialText,
+        TextBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font_size: 48.,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(64.),
+                width: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        }
+        .with_text_justify(JustifyText::Center),
+    ));
+}
+
+fn system_tutorial(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut tutorial: ResMut<Tutorial>,
+    mut tutorial_done: ResMut<TutorialDone>,
+    mut events: EventReader<GameEvent>,
+    bindings: Res<KeyBindings>,
+    mut source: LevelSource,
+    mut run: RunReset,
+    mut state: ResMut<NextState<Game>>,
+    mut texts: Query<&mut Text, With<TutorialText>>,
+    level_entities: Query<Entity, (With<LevelEntity>, Without<Parent>)>,
+) {
+    for event in events.read() {
+        if let Some(step) = TUTORIAL_STEPS.get(tutorial.step) {
+            if step.goal.reached_by(event) {
+                tutorial.step += 1;
+            }
+        }
+    }
+
+    let Some(step) = TUTORIAL_STEPS.get(tutorial.step) else {
+        tutorial_done.complete();
+        for entity in &level_entities {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        run.reset();
+        source.current_level.0 = 0;
+        source.spawn(&mut commands, &assets);
+        state.set(Game::Countdown);
+        return;
+    };
+
+    let grab = bindings
+        .key(Action::Grab)
+        .map(key_label)
+        .unwrap_or_default();
+    let prompt = step.prompt.replace("{grab}", &grab);
+    for mut text in &mut texts {
+        if text.sections[0].value != prompt {
+            text.sections[0].value.clone_from(&prompt);
+        }
+    }
+}
+
+fn system_end_tutorial(mut commands: Commands, query: Query<Entity, With<TutorialText>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Whether the hands are moving, in a real level or the tutorial.
+fn in_gameplay(state: Option<Res<State<Game>>>) -> bool {
+    state.is_some_and(|state| matches!(state.get(), Game::Playing | Game::Tutorial))
+}
+
+fn system_toggle_pause(
+    action_input: Res<ActionInput>,
+    state: Res<State<Game>>,
+    mut next_state: ResMut<NextState<Game>>,
+) {
+    if !action_input.just_pressed(Action::Pause) {
+        return;
+    }
+
+    match state.get() {
+        Game::Playing => next_state.set(Game::Paused),
+        Game::Paused => next_state.set(Game::Playing),
+        _ => {}
+    }
+}
+
+const LEVEL_COMPLETE_DURATION: f32 = 2.;
+
+#[derive(Resource)]
+struct LevelCompleteTimer(Timer);
+
+impl Default for LevelCompleteTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            LEVEL_COMPLETE_DURATION,
+            TimerMode::Once,
+        ))
+    }
+}
+
+fn system_show_level_complete(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    current_level: Res<CurrentLevel>,
+    mut timer: ResMut<LevelCompleteTimer>,
+) {
+    timer.0.reset();
+    spawn_banner(
+        &mut commands,
+        &assets.font,
+        format!("LEVEL {} COMPLETE", current_level.0 + 1),
+    );
+}
+
+fn system_load_next_level(
+    mut commands: Commands,
+    time: Res<Time>,
+    assets: Res<GameAssets>,
+    mut source: LevelSource,
+    mut timer: ResMut<LevelCompleteTimer>,
+    mut run_timer: ResMut<RunTimer>,
+    mut state: ResMut<NextState<Game>>,
+    level_entities: Query<Entity, (With<LevelEntity>, Without<Parent>)>,
+    banners: Query<Entity, With<Banner>>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    for entity in level_entities.iter().chain(banners.iter()) {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    commands.remove_resource::<FinishShown>();
+    // Every level is timed, and scored, on its own.
+    run_timer.0.reset();
+    source.current_level.0 += 1;
+    source.spawn(&mut commands, &assets);
+    state.set(Game::Countdown);
+}
+
+/// Asks for the current level to be restarted regardless of the game state.
+#[derive(Event)]
+pub struct RestartLevel;
+
+/// Per-run state that is cleared when a level restarts.
+#[derive(SystemParam)]
+struct RunReset<'w> {
+    overlap: ResMut<'w, Overlap>,
+    focus: ResMut<'w, CameraFocus>,
+    combo: ResMut<'w, Combo>,
+    run_timer: ResMut<'w, RunTimer>,
+    drop_timer: ResMut<'w, DropTimer>,
+    buffer: ResMut<'w, GrabBuffer>,
+    last_holder: ResMut<'w, LastHolder>,
+    checkpoint: ResMut<'w, CheckpointState>,
+    stats: ResMut<'w, RunStats>,
+    score: ResMut<'w, Score>,
+}
+
+impl RunReset<'_> {
+    fn reset(&mut self) {
+        self.overlap.clear();
+        *self.focus = CameraFocus::default();
+        *self.combo = Combo::default();
+        self.run_timer.0.reset();
+        self.drop_timer.0 = None;
+        self.buffer.clear();
+        self.last_holder.0 = None;
+        self.checkpoint.hand = None;
+        *self.stats = RunStats::default();
+        self.score.0 = 0;
+    }
+}
+
+/// Restarting with the key goes through [`RestartLevel`] too, so everyone listening hears of it.
+fn system_request_restart(
+    action_input: Res<ActionInput>,
+    game: Res<State<Game>>,
+    mut restart: EventWriter<RestartLevel>,
+) {
+    if action_input.just_pressed(Action::Restart)
+        && matches!(game.get(), Game::Finished | Game::Failed)
+    {
+        restart.send(RestartLevel);
+    }
+}
+
+fn system_restart(
+    mut commands: Commands,
+    mut requests: EventReader<RestartLevel>,
+    assets: Res<GameAssets>,
+    mut source: LevelSource,
+    mut run: RunReset,
+    mut state: ResMut<NextState<Game>>,
+    level_entities: Query<Entity, (With<LevelEntity>, Without<Parent>)>,
+    banners: Query<Entity, With<Banner>>,
+    after_images: Query<Entity, With<AfterImage>>,
+    mut live: ResMut<AfterImageRing>,
+    mut pool: ResMut<AfterImagePool>,
+) {
+    if requests.read().count() == 0 {
+        return;
+    }
+
+    for entity in level_entities
+        .iter()
+        .chain(banners.iter())
+        .chain(after_images.iter())
+    {
+        commands.entity(entity).despawn_recursive();
+    }
+    live.0.clear();
+    pool.0.clear();
+
+    run.reset();
+    commands.remove_resource::<FinishShown>();
+
+    source.spawn(&mut commands, &assets);
+    state.set(Game::Countdown);
+}
+
+/// Tears down the run when going back to the main menu, so the next run starts fresh.
+fn system_leave_game(
+    mut commands: Commands,
+    mut run: RunReset,
+    level_entities: Query<Entity, (With<LevelEntity>, Without<Parent>)>,
+    banners: Query<Entity, With<Banner>>,
+    after_images: Query<Entity, With<AfterImage>>,
+    mut live: ResMut<AfterImageRing>,
+    mut pool: ResMut<AfterImagePool>,
+    music: Query<Entity, With<Music>>,
+) {
+    for entity in level_entities
+        .iter()
+        .chain(banners.iter())
+        .chain(after_images.iter())
+        .chain(music.iter())
+    {
+        commands.entity(entity).despawn_recursive();
+    }
+    live.0.clear();
+    pool.0.clear();
+
+    run.reset();
+    commands.remove_resource::<FinishShown>();
+}
+
+fn system_play_finish_sound(mut commands: Commands, assets: Res<GameAssets>, volume: Res<Volume>) {
+    commands.spawn(AudioBundle {
+        source: assets.finish_sfx.clone(),
+        settings: PlaybackSettings::ONCE.with_volume(AudioVolume::new(volume.sfx())),
+    });
+}
+
+#[derive(Component)]
+struct Music;
+
+fn system_start_music(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    volume: Res<Volume>,
+    playing: Query<(), With<Music>>,
+) {
+    if !playing.is_empty() {
+        return;
+    }
+
+    commands.spawn((
+        Music,
+        AudioBundle {
+            source: assets.music.clone(),
+            settings: PlaybackSettings::LOOP.with_volume(AudioVolume::new(volume.music())),
+        },
+    ));
+}
+
+fn system_stop_music(mut commands: Commands, query: Query<Entity, With<Music>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Gameplay systems grouped by the data they produce, in the order it flows.
+/// Input runs in `PreUpdate`, collision and grabbing in `FixedUpdate`, and visuals
+/// in `Update`, where they see the grab results of every fixed step this frame.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GameSet {
+    /// Turning key presses into buffered grabs, latched each frame in `PreUpdate` and
+    /// buffered each fixed step in `FixedUpdate`.
+    Input,
+    /// Moving the hands and cycles, then placing colliders where they ended up.
+    Movement,
+    /// Finding which colliders overlap after the hands have moved.
+    Collision,
+    /// Acting on buffered grabs using fresh overlaps.
+    Grab,
+    /// Placing and tinting sprites to match the state left by grabs.
+    Visuals,
+    /// Moving and zooming the cameras to follow play.
+    Camera,
+}
+
+pub struct GameBundle;
+
+impl Plugin for GameBundle {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Overlap>()
+            .init_resource::<AfterImageRing>()
+            .init_resource::<AfterImagePool>()
+            .init_resource::<AfterImagePalette>()
+            .init_resource::<ColorScheme>()
+            .add_systems(Update, system_apply_color_scheme)
+            .init_resource::<DropTimer>()
+            .init_resource::<DirectionalHandover>()
+            .init_resource::<RunTimer>()
+            .init_resource::<Combo>()
+            .init_resource::<GrabBuffer>()
+            .init_resource::<LastHolder>()
+            .init_resource::<SlowMoTimer>()
+            .add_systems(Update, system_slow_mo)
+            .init_resource::<AutoGrab>()
+            .init_resource::<GrabCooldown>()
+            .init_resource::<Levels>()
+            .init_resource::<LevelSeed>()
+            .init_resource::<GameMode>()
+            .init_resource::<EndlessFrontier>()
+            .init_resource::<EndlessDistance>()
+            .init_resource::<GameRng>()
+            .init_resource::<CurrentLevel>()
+            .init_resource::<Difficulty>()
+            .init_resource::<RunDifficulty>()
+            .add_systems(Startup, system_apply_launch_options)
+            .init_resource::<LevelCompleteTimer>()
+            .init_resource::<CountdownTimer>()
+            .init_resource::<GameAssets>()
+            .init_resource::<Volume>()
+            .add_event::<GameEvent>()
+            .add_event::<LevelCompleted>()
+            .add_event::<RestartLevel>()
+            .observe(on_add_active)
+            .observe(on_insert_grip)
+            .add_systems(Update, system_animate_hands)
+            .observe(on_finish)
+            .init_resource::<CameraFocus>()
+            .init_resource::<CameraZoom>()
+            .init_resource::<CameraConfig>()
+            .init_resource::<LevelBounds>()
+            .init_resource::<CameraLead>()
+            .init_resource::<CameraDeadZone>()
+            .add_systems(
+                Update,
+                system_update_camera_lead.before(system_lerp_camera_to_focus),
+            )
+            .add_systems(
+                PostUpdate,
+                system_update_level_bounds.after(TransformSystem::TransformPropagate),
+            )
+            .init_resource::<CameraShake>()
+            .init_state::<AppState>()
+            .add_sub_state::<Game>()
+            .enable_state_scoped_entities::<AppState>()
+            .add_systems(Startup, system_setup_camera)
+            .init_resource::<BackgroundGradient>()
+            .add_systems(Startup, system_setup_background)
+            .add_systems(
+                Update,
+                system_update_background.after(system_apply_camera_shake),
+            )
+            .add_systems(OnEnter(Game::Loading), system_show_loading)
+            .add_systems(
+                Update,
+                (system_check_loading, system_check_asset_failures).run_if(in_state(Game::Loading)),
+            )
+            .add_systems(OnExit(Game::Loading), system_hide_loading)
+            .add_systems(OnEnter(AppState::InGame), system_setup_combo_text)
+            .add_systems(OnEnter(AppState::InGame), system_setup_run_timer_text)
+            .add_systems(OnEnter(AppState::InGame), system_setup_finish_distance_text)
+            .add_systems(Update, system_show_finish_distance.run_if(in_gameplay))
+            .add_systems(OnEnter(AppState::InGame), system_setup_minimap)
+            .add_systems(Update, system_update_minimap)
+            .add_systems(OnEnter(AppState::InGame), system_setup_distance_text)
+            .add_systems(OnEnter(AppState::InGame), system_setup_control_prompt)
+            .add_systems(Update, system_show_control_prompt)
+            .configure_sets(PreUpdate, GameSet::Input.after(input::read_input))
+            .configure_sets(FixedUpdate, GameSet::Input.after(input::begin_step))
+            .configure_sets(
+                FixedUpdate,
+                (
+                    GameSet::Input,
+                    GameSet::Movement,
+                    GameSet::Collision,
+                    GameSet::Grab,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                PreUpdate,
+                (system_auto_grab.run_if(in_gameplay), system_latch_presses)
+                    .chain()
+                    .in_set(GameSet::Input),
+            )
+            .add_systems(FixedUpdate, system_buffer_grab.in_set(GameSet::Input))
+            .add_systems(
+                FixedUpdate,
+                (
+                    system_store_previous_progress,
+                    system_progress.run_if(in_gameplay),
+                    system_move_cycles.run_if(in_gameplay),
+                    system_place_colliders,
+                )
+                    .chain()
+                    .in_set(GameSet::Movement),
+            )
+            .add_systems(FixedUpdate, system_check_overlap.in_set(GameSet::Collision))
+            .add_systems(
+                FixedUpdate,
+                (
+                    system_grab_toggle.run_if(in_gameplay),
+                    system_check_hazards.run_if(in_gameplay),
+                    system_collect_pickups.run_if(in_gameplay),
+                )
+                    .chain()
+                    .in_set(GameSet::Grab),
+            )
+            .add_systems(PreUpdate, system_reseed_rng)
+            .add_systems(
+                Update,
+                system_cycle_hand
+                    .run_if(in_gameplay.or_else(in_state(Game::Countdown)))
+                    .in_set(GameSet::Visuals),
+            )
+            .add_systems(Update, system_draw_progress_ring.run_if(in_gameplay))
+            .add_systems(Update, system_finish_arrow.run_if(in_gameplay))
+            .add_systems(
+                Update,
+                system_highlight_grabbable
+                    .run_if(in_gameplay)
+                    .in_set(GameSet::Visuals),
+            )
+            .add_systems(
+                Update,
+                system_tint_layers
+                    .after(system_set_render_layer)
+                    .after(system_apply_color_scheme)
+                    .run_if(in_gameplay)
+                    .in_set(GameSet::Visuals),
+            )
+            .init_resource::<Tutorial>()
+            .init_resource::<TutorialDone>()
+            .add_systems(OnEnter(Game::Tutorial), system_start_tutorial)
+            .add_systems(Update, system_tutorial.run_if(in_state(Game::Tutorial)))
+            .add_systems(OnExit(Game::Tutorial), system_end_tutorial)
+            .add_systems(OnEnter(Game::Countdown), system_start_countdown)
+            .add_systems(Update, system_countdown.run_if(in_state(Game::Countdown)))
+            .add_systems(OnExit(Game::Countdown), system_end_countdown)
+            .add_systems(
+                Update,
+                system_toggle_pause.run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(Update, system_play_sfx)
+            .add_systems(Update, system_rumble)
+            .add_systems(Update, system_apply_volume)
+            .add_systems(Update, system_update_combo)
+            .init_resource::<RunStats>()
+            .init_resource::<Score>()
+            .add_systems(
+                Update,
+                (system_spawn_score_popups, system_animate_score_popups).chain(),
+            )
+            .add_systems(Update, system_update_run_stats.after(system_update_combo))
+            .add_systems(Update, system_show_combo)
+            .add_systems(
+                Update,
+                system_update_run_timer.run_if(in_state(Game::Playing)),
+            )
+            .add_systems(Update, system_show_run_timer)
+            .init_resource::<GhostReplay>()
+            .add_systems(OnEnter(Game::Countdown), system_start_ghost)
+            .add_systems(
+                Update,
+                (system_record_ghost, system_play_ghost)
+                    .chain()
+                    .run_if(in_state(Game::Playing)),
+            )
+            .add_systems(OnEnter(Game::LevelComplete), system_store_ghost)
+            .add_systems(OnEnter(Game::Finished), system_store_ghost)
+            .add_systems(
+                Update,
+                system_extend_endless.run_if(in_state(Game::Playing)),
+            )
+            .add_systems(Update, system_show_distance)
+            .add_systems(Update, system_lerp_camera_to_focus.in_set(GameSet::Camera))
+            .add_systems(Update, system_bump_camera_shake)
+            .add_systems(
+                Update,
+                system_remove_camera_shake.before(system_lerp_camera_to_focus),
+            )
+            .add_systems(
+                Update,
+                system_apply_camera_shake.after(system_lerp_camera_to_focus),
+            )
+            .add_systems(Update, system_update_camera_zoom)
+            .add_systems(Update, system_lerp_camera_zoom.in_set(GameSet::Camera))
+            .add_systems(
+                Update,
+                (system_lerp_item_to_holding, system_set_render_layer).in_set(GameSet::Visuals),
+            )
+            .add_systems(
+                Update,
+                (system_after_images, system_baton_trail)
+                    .run_if(in_gameplay)
+                    .in_set(GameSet::Visuals),
+            )
+            .add_systems(Update, fade_out_after_images)
             .add_systems(
                 Update,
                 system_fade_out_everything.run_if(in_state(Game::Finished)),
@@ -721,8 +4396,530 @@ impl Plugin for GameBundle {
                 Update,
                 system_magnify_baton.run_if(in_state(Game::Finished)),
             )
+            .add_systems(OnEnter(Game::LevelComplete), system_show_level_complete)
+            .add_systems(
+                Update,
+                system_load_next_level.run_if(in_state(Game::LevelComplete)),
+            )
+            .add_systems(Update, system_drop_timer.run_if(in_state(Game::Playing)))
+            .init_resource::<CheckpointsEnabled>()
+            .init_resource::<CheckpointState>()
+            .add_systems(Update, system_capture_checkpoint)
+            .add_systems(OnEnter(Game::Failed), system_show_failed_text)
+            .add_systems(
+                Update,
+                system_continue_from_checkpoint.run_if(in_state(Game::Failed)),
+            )
+            .add_systems(
+                Update,
+                (system_request_restart, system_restart)
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(OnExit(AppState::InGame), system_leave_game)
+            .init_resource::<Strings>()
             .add_systems(OnEnter(Game::Finished), system_show_finish_text)
+            .add_systems(
+                Update,
+                system_fade_in_finish_text.run_if(in_state(Game::Finished)),
+            )
             .add_systems(OnEnter(Game::Finished), system_play_finish_sound)
-            .add_systems(PostUpdate, system_clean_up_sfx);
+            .add_systems(OnEnter(Game::Playing), system_start_music)
+            .add_systems(OnEnter(Game::Finished), system_stop_music)
+            .add_systems(PostUpdate, system_clean_up_sfx::<AudioSink>);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::world::CommandQueue;
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+    use crate::replay::ReplayPlayer;
+
+    /// One fixed step, so every update after the first runs `FixedUpdate` exactly once.
+    const STEP: Duration = Duration::from_micros(15_625);
+
+    /// A headless app with time advancing by [`STEP`] per update.
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, HierarchyPlugin, TransformPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(STEP));
+        app
+    }
+
+    /// Every asset as an empty handle, enough to spawn levels without loading anything.
+    fn assets() -> GameAssets {
+        GameAssets {
+            hand_open: default(),
+            hand_closed: default(),
+            hand_closed_thumb: default(),
+            hand_atlas: None,
+            cycle: default(),
+            finish: default(),
+            baton: default(),
+            hand_over_sfx: default(),
+            select_sfx: default(),
+            select_miss_sfx: default(),
+            finish_sfx: default(),
+            music: default(),
+            font: default(),
+        }
+    }
+
+    /// Runs the grab pipeline the way [`GameBundle`] does, on a level spawned from a
+    /// [`LevelDef`], with presses synthesized straight into [`ActionInput`].
+    struct GrabHarness(App);
+
+    impl GrabHarness {
+        fn new(level: &LevelDef) -> Self {
+            let mut app = app();
+            app.init_resource::<ActionInput>()
+                .init_resource::<Overlap>()
+                .init_resource::<DirectionalHandover>()
+                .init_resource::<StepInput>()
+                .init_resource::<KeyBindings>()
+                .init_resource::<ReplayPlayer>()
+                .init_resource::<GrabBuffer>()
+                .init_resource::<GrabCooldown>()
+                .init_resource::<LastHolder>()
+                .init_resource::<SlowMoTimer>()
+                .add_event::<GameEvent>()
+                .add_systems(PreUpdate, system_latch_presses)
+                .add_systems(
+                    FixedUpdate,
+                    (
+                        input::begin_step,
+                        system_buffer_grab,
+                        system_place_colliders,
+                        system_check_overlap,
+                        system_grab_toggle,
+                    )
+                        .chain(),
+                );
+
+            let world = app.world_mut();
+            let mut queue = CommandQueue::default();
+            let mut commands = Commands::new(&mut queue, world);
+            spawn_level(&mut commands, &assets(), level, Difficulty::Normal);
+            queue.apply(world);
+
+            // The first update only starts the clock, it doesn't run a fixed step.
+            app.update();
+            Self(app)
+        }
+
+        fn step(&mut self) {
+            self.0.update();
+            // Like `read_input`, presses only last for the frame they were made in.
+            self.0.world_mut().insert_resource(ActionInput::default());
+        }
+
+        fn wait_out_cooldown(&mut self) {
+            for _ in 0..=GRAB_COOLDOWN.as_micros() / STEP.as_micros() {
+                self.step();
+            }
+        }
+
+        fn press_grab(&mut self) {
+            self.0
+                .world_mut()
+                .resource_mut::<ActionInput>()
+                .press(Action::Grab);
+            self.step();
+        }
+
+        fn active(&mut self) -> Entity {
+            self.0
+                .world_mut()
+                .query_filtered::<Entity, (With<Hand>, With<Active>)>()
+                .single(self.0.world())
+        }
+
+        fn baton(&mut self) -> Entity {
+            self.0
+                .world_mut()
+                .query_filtered::<Entity, With<Item>>()
+                .single(self.0.world())
+        }
+
+        fn grip(&self, entity: Entity) -> Grip {
+            *self.0.world().get::<Grip>(entity).unwrap()
+        }
+
+        fn set_progress(&mut self, entity: Entity, progress: f32) {
+            self.0.world_mut().get_mut::<Progress>(entity).unwrap().0 = progress;
+        }
+
+        /// The hands of the cycles placed in the level, in order.
+        fn hands(&mut self) -> Vec<Entity> {
+            let world = self.0.world_mut();
+            let mut cycles = world.query_filtered::<&Transform, With<Cycle>>();
+            let mut hands = world
+                .query_filtered::<(Entity, &Parent), With<Hand>>()
+                .iter(world)
+                .map(|(hand, cycle)| {
+                    let x = cycles.get(world, cycle.get()).unwrap().translation.x;
+                    (hand, x)
+                })
+                .collect::<Vec<_>>();
+            hands.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+            hands.into_iter().map(|(hand, _)| hand).collect()
+        }
+
+        /// Whether a [`GameEvent`] matching `sent` went out in the last couple of updates.
+        fn sent(&self, sent: impl Fn(&GameEvent) -> bool) -> bool {
+            let events = self.0.world().resource::<Events<GameEvent>>();
+            events.get_reader().read(events).any(sent)
+        }
+    }
+
+    /// A starting cycle whose hand begins right on the baton, with a second cycle after it.
+    fn relay_level() -> LevelDef {
+        LevelDef {
+            places: vec![
+                Place::Baton(vec2(-0.5, 0.)),
+                Place::CycleStart(vec2(0., 0.), 1.),
+                Place::Cycle(vec2(1., 0.), 1., 1),
+            ],
+            ..default()
+        }
+    }
+
+    #[test]
+    fn grab_picks_up_the_overlapping_baton() {
+        let mut harness = GrabHarness::new(&relay_level());
+        let hand = harness.active();
+        let baton = harness.baton();
+
+        harness.press_grab();
+
+        assert_eq!(harness.grip(hand), Grip::Holding(baton));
+        assert_eq!(
+            harness.0.world().get::<Parent>(baton).map(Parent::get),
+            Some(hand)
+        );
+        assert!(harness.sent(|event| matches!(event, GameEvent::Grab)));
+    }
+
+    #[test]
+    fn grab_out_of_reach_misses_once_the_buffer_runs_out() {
+        let mut harness = GrabHarness::new(&relay_level());
+        let hand = harness.active();
+        // A quarter turn puts the hand well above the baton.
+        harness.set_progress(hand, 0.25);
+
+        harness.press_grab();
+        assert_eq!(harness.grip(hand), Grip::Empty);
+
+        for _ in 0..GRAB_BUFFER_WINDOW.as_micros() / STEP.as_micros() + 1 {
+            harness.step();
+        }
+        assert_eq!(harness.grip(hand), Grip::Reaching);
+    }
+
+    #[test]
+    fn handover_passes_the_baton_and_the_active_hand() {
+        let mut harness = GrabHarness::new(&relay_level());
+        let [from, to] = harness.hands()[..] else {
+            panic!("expected two hands");
+        };
+        let baton = harness.baton();
+        harness.press_grab();
+        harness.wait_out_cooldown();
+
+        // Facing each other across the gap between the two cycles.
+        harness.set_progress(from, 0.);
+        harness.set_progress(to, 0.5);
+        harness.step();
+        harness.press_grab();
+
+        assert_eq!(harness.active(), to);
+        assert_eq!(harness.grip(to), Grip::Holding(baton));
+        assert_eq!(harness.grip(from), Grip::Empty);
+        assert!(harness.sent(|event| matches!(event, GameEvent::HandOver { .. })));
+    }
+
+    #[test]
+    fn drop_resets_the_combo() {
+        let mut app = app();
+        app.init_resource::<Combo>()
+            .add_event::<GameEvent>()
+            .add_systems(Update, system_update_combo);
+
+        for _ in 0..3 {
+            app.world_mut().send_event(GameEvent::HandOver {
+                quality: Quality::Good,
+            });
+        }
+        app.update();
+        assert_eq!(app.world().resource::<Combo>().count, 3);
+
+        app.world_mut().send_event(GameEvent::Drop);
+        app.update();
+        assert_eq!(app.world().resource::<Combo>().count, 0);
+    }
+
+    #[test]
+    fn reparenting_the_baton_does_not_make_it_jump() {
+        let mut app = app();
+        app.add_systems(Update, system_lerp_item_to_holding);
+
+        let from = app
+            .world_mut()
+            .spawn((SpatialBundle::default(), Grip::Empty))
+            .id();
+        // Turned and scaled, so a reparent that isn't done in world space would show.
+        let to = app
+            .world_mut()
+            .spawn((
+                SpatialBundle::from_transform(
+                    Transform::from_xyz(300., 100., 0.)
+                        .with_rotation(Quat::from_rotation_z(PI / 2.))
+                        .with_scale(Vec3::splat(2.)),
+                ),
+                Grip::Empty,
+            ))
+            .id();
+        let baton = app
+            .world_mut()
+            .spawn((
+                Item,
+                SpatialBundle::from_transform(Transform::from_xyz(50., 0., 0.)),
+            ))
+            .set_parent(from)
+            .id();
+        app.world_mut()
+            .entity_mut(from)
+            .insert(Grip::Holding(baton));
+        app.update();
+
+        let before = app
+            .world()
+            .get::<GlobalTransform>(baton)
+            .unwrap()
+            .translation();
+        app.world_mut().entity_mut(from).insert(Grip::Empty);
+        app.world_mut().entity_mut(to).insert(Grip::Holding(baton));
+        let world = app.world_mut();
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, world)
+            .entity(baton)
+            .set_parent_in_place(to);
+        queue.apply(world);
+        app.update();
+
+        let after = app
+            .world()
+            .get::<GlobalTransform>(baton)
+            .unwrap()
+            .translation();
+        let target = Vec3::new(300., 100., 0.);
+        // Only the usual easing toward the new holder, nothing more.
+        assert!(after.distance(before) <= before.distance(target) * 0.03 + 0.01);
+        assert!(after.distance(target) < before.distance(target));
+    }
+
+    #[test]
+    fn after_image_interval_is_clamped_at_both_ends() {
+        assert_eq!(after_image_interval(1000.), Duration::from_millis(16));
+        assert_eq!(after_image_interval(0.001), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn a_fast_fading_after_image_never_shrinks_below_zero() {
+        let mut app = app();
+        app.init_resource::<AfterImageRing>()
+            .init_resource::<AfterImagePool>()
+            .add_systems(Update, fade_out_after_images);
+
+        let image = app
+            .world_mut()
+            .spawn((
+                AfterImage,
+                FadeOutSpeed(1e6),
+                SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::splat(64.)),
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .id();
+        app.update();
+        app.update();
+
+        let size = app.world().get::<Sprite>(image).unwrap().custom_size;
+        assert!(size.is_some_and(|size| size.cmpge(Vec2::ZERO).all()));
+        assert_eq!(app.world().resource::<AfterImagePool>().0, [image]);
+    }
+
+    /// A sink that has either run out of sound or not.
+    #[derive(Component)]
+    struct FakeSink {
+        empty: bool,
+    }
+
+    impl AudioSinkPlayback for FakeSink {
+        fn volume(&self) -> f32 {
+            1.
+        }
+        fn set_volume(&self, _volume: f32) {}
+        fn speed(&self) -> f32 {
+            1.
+        }
+        fn set_speed(&self, _speed: f32) {}
+        fn play(&self) {}
+        fn pause(&self) {}
+        fn is_paused(&self) -> bool {
+            false
+        }
+        fn stop(&self) {}
+        fn empty(&self) -> bool {
+            self.empty
+        }
+    }
+
+    #[test]
+    fn an_empty_sink_is_despawned() {
+        let mut app = app();
+        app.add_systems(PostUpdate, system_clean_up_sfx::<FakeSink>);
+
+        let finished = app.world_mut().spawn(FakeSink { empty: true }).id();
+        let playing = app.world_mut().spawn(FakeSink { empty: false }).id();
+        app.update();
+
+        assert!(app.world().get_entity(finished).is_none());
+        assert!(app.world().get_entity(playing).is_some());
+    }
+
+    #[test]
+    fn a_nested_hand_going_active_focuses_its_cycle() {
+        let mut app = app();
+        app.init_resource::<CameraFocus>().observe(on_add_active);
+
+        let cycle = app
+            .world_mut()
+            .spawn((
+                Cycle,
+                SpatialBundle::from_transform(Transform::from_xyz(200., -50., 0.)),
+            ))
+            .id();
+        let arm = app
+            .world_mut()
+            .spawn(SpatialBundle::from_transform(Transform::from_xyz(
+                30., 0., 0.,
+            )))
+            .set_parent(cycle)
+            .id();
+        let hand = app
+            .world_mut()
+            .spawn(SpatialBundle::from_transform(Transform::from_xyz(
+                0., 20., 0.,
+            )))
+            .set_parent(arm)
+            .id();
+        app.update();
+
+        app.world_mut().entity_mut(hand).insert(Active);
+
+        assert_eq!(app.world().resource::<CameraFocus>().0, vec2(200., -50.));
+    }
+
+    #[test]
+    fn progress_wraps_both_ways() {
+        let mut progress = Progress(0.9);
+        progress.advance(0.2);
+        assert!((progress.0 - 0.1).abs() < 1e-5);
+
+        progress.advance(-0.3);
+        assert!((progress.0 - 0.8).abs() < 1e-5);
+
+        progress.advance(-2.);
+        assert!((progress.0 - 0.8).abs() < 1e-5);
+    }
+
+    #[test]
+    fn progress_angle_is_in_radians() {
+        assert_eq!(Progress(0.).angle(), 0.);
+        assert_eq!(Progress(0.25).angle(), PI / 2.);
+        assert_eq!(Progress(0.5).angle(), PI);
+    }
+
+    #[test]
+    fn high_contrast_recolors_cycles() {
+        let mut app = app();
+        app.init_resource::<ColorScheme>()
+            .init_resource::<AfterImagePalette>()
+            .add_systems(
+                Update,
+                (system_apply_color_scheme, system_tint_layers).chain(),
+            );
+
+        let cycle = app
+            .world_mut()
+            .spawn((
+                Cycle,
+                CycleColor(0),
+                SpriteBundle::default(),
+                RenderLayers::layer(LAYER_INACTIVE),
+            ))
+            .id();
+        app.update();
+        let before = app.world().get::<Sprite>(cycle).unwrap().color;
+
+        *app.world_mut().resource_mut::<ColorScheme>() = ColorScheme::HighContrast;
+        app.update();
+
+        let after = app.world().get::<Sprite>(cycle).unwrap().color;
+        assert_ne!(after, before);
+        assert_eq!(after, HIGH_CONTRAST_COLOR[0].darker(CYCLE_INACTIVE_DARKEN));
+    }
+
+    #[test]
+    fn a_moving_cycle_starts_in_the_same_phase_however_late_it_spawns() {
+        let mut app = app();
+        app.add_systems(FixedUpdate, system_move_cycles);
+        for _ in 0..100 {
+            app.update();
+        }
+
+        let cycle = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                CycleMotion {
+                    from: Vec2::ZERO,
+                    to: vec2(100., 0.),
+                    period: 4.,
+                    elapsed: 0.,
+                },
+            ))
+            .id();
+        app.update();
+
+        let x = app.world().get::<Transform>(cycle).unwrap().translation.x;
+        assert!(x < 0.1, "cycle started {x} along its path");
+    }
+
+    #[test]
+    fn a_handover_press_during_the_grab_cooldown_is_ignored() {
+        let mut harness = GrabHarness::new(&relay_level());
+        let [from, to] = harness.hands()[..] else {
+            panic!("expected two hands");
+        };
+        let baton = harness.baton();
+        harness.press_grab();
+
+        harness.set_progress(from, 0.);
+        harness.set_progress(to, 0.5);
+        harness.step();
+        harness.press_grab();
+
+        assert_eq!(harness.active(), from);
+        assert_eq!(harness.grip(from), Grip::Holding(baton));
+        assert_eq!(harness.grip(to), Grip::Empty);
     }
 }
@@ -1,19 +1,59 @@
 use bevy::prelude::*;
 
+use crate::replay::ReplayPlayer;
+
+/// Every action is numbered by hand, since the number picks its bit in recorded replays.
+/// Give new actions the next free number and never renumber existing ones.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Action {
-    Grab,
-    DebugShowCollisions,
+    Grab = 0,
+    DebugShowCollisions = 1,
+    DebugShowGrid = 2,
+    DebugShowOverlaps = 3,
+    DebugShowHud = 4,
+    DebugSlower = 5,
+    DebugFaster = 6,
+    DebugFreeCam = 7,
+    DebugPause = 8,
+    DebugStep = 9,
+    DebugLogEvents = 10,
+    Pause = 11,
+    Restart = 12,
+    Continue = 13,
+    Settings = 14,
+    SaveReplay = 15,
+    PlayReplay = 16,
+    MenuUp = 17,
+    MenuDown = 18,
+    MenuConfirm = 19,
+}
+
+impl Action {
+    /// The bit this action occupies in [`ActionInput::just_pressed_bits`].
+    pub fn bit(self) -> u32 {
+        1 << self as u32
+    }
 }
 
 struct ActionState {
     action: Action,
     pressed: bool,
     just_pressed: bool,
+    #[allow(dead_code)]
     just_released: bool,
 }
 
 impl ActionState {
+    fn replayed(action: Action, bits: u32) -> ActionState {
+        let just_pressed = bits & action.bit() != 0;
+        Self {
+            action,
+            pressed: just_pressed,
+            just_pressed,
+            just_released: false,
+        }
+    }
+
     fn read((action, key): (Action, KeyCode), input: &ButtonInput<KeyCode>) -> ActionState {
         Self {
             action,
@@ -24,25 +64,8 @@ impl ActionState {
     }
 }
 
-impl Action {
-    fn state(self) -> ActionState {
-        ActionState {
-            action: self,
-            pressed: false,
-            just_pressed: false,
-            just_released: false,
-        }
-    }
-}
-
-#[derive(Resource)]
-pub struct ActionInput([ActionState; 2]);
-
-impl Default for ActionInput {
-    fn default() -> Self {
-        Self([Action::Grab.state(), Action::DebugShowCollisions.state()])
-    }
-}
+#[derive(Resource, Default)]
+pub struct ActionInput(Vec<ActionState>);
 
 impl ActionInput {
     pub fn just_pressed(&self, action: Action) -> bool {
@@ -53,6 +76,8 @@ impl ActionInput {
             .unwrap_or(false)
     }
 
+    // Part of the query API alongside `just_pressed`, even though no action is held yet.
+    #[allow(dead_code)]
     pub fn pressed(&self, action: Action) -> bool {
         self.0
             .iter()
@@ -61,6 +86,25 @@ impl ActionInput {
             .unwrap_or(false)
     }
 
+    /// Marks `action` as just pressed this frame, as if its key had been hit.
+    pub fn press(&mut self, action: Action) {
+        match self.0.iter_mut().find(|state| state.action == action) {
+            Some(state) => {
+                state.pressed = true;
+                state.just_pressed = true;
+            }
+            None => self.0.push(ActionState::replayed(action, action.bit())),
+        }
+    }
+
+    pub fn just_pressed_bits(&self) -> u32 {
+        self.0
+            .iter()
+            .filter(|state| state.just_pressed)
+            .fold(0, |bits, state| bits | state.action.bit())
+    }
+
+    #[allow(dead_code)]
     pub fn just_released(&self, action: Action) -> bool {
         self.0
             .iter()
@@ -70,14 +114,137 @@ impl ActionInput {
     }
 }
 
-fn read_input(buttons: Res<ButtonInput<KeyCode>>, mut action_input: ResMut<ActionInput>) {
-    let mappings = [
-        (Action::Grab, KeyCode::Space),
-        (Action::DebugShowCollisions, KeyCode::KeyD),
-    ];
+/// Presses handed from frames to fixed steps. Gameplay in `FixedUpdate` reads its presses
+/// from here, so a replay feeding the same presses to the same steps plays back the same
+/// run at any frame rate.
+#[derive(Resource, Default)]
+pub struct StepInput {
+    /// Packed [`Action::bit`]s pressed in frames since the last fixed step.
+    latched: u32,
+    /// Packed [`Action::bit`]s pressed for the current fixed step.
+    pub just_pressed: u32,
+}
+
+impl StepInput {
+    /// Keeps `bits` for the next fixed step, however many frames pass before it runs.
+    pub fn latch(&mut self, bits: u32) {
+        self.latched |= bits;
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed & action.bit() != 0
+    }
+}
+
+#[derive(Resource)]
+pub struct KeyBindings(Vec<(Action, KeyCode)>);
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self(vec![
+            (Action::Grab, KeyCode::Space),
+            (Action::DebugShowCollisions, KeyCode::Digit1),
+            (Action::DebugShowGrid, KeyCode::Digit2),
+            (Action::DebugShowOverlaps, KeyCode::Digit3),
+            (Action::DebugShowHud, KeyCode::Digit4),
+            (Action::DebugSlower, KeyCode::Minus),
+            (Action::DebugFaster, KeyCode::Equal),
+            (Action::DebugFreeCam, KeyCode::Digit5),
+            (Action::DebugPause, KeyCode::Digit6),
+            (Action::DebugStep, KeyCode::Digit7),
+            (Action::DebugLogEvents, KeyCode::Digit8),
+            (Action::Pause, KeyCode::Escape),
+            (Action::Restart, KeyCode::KeyR),
+            (Action::Continue, KeyCode::KeyC),
+            (Action::Settings, KeyCode::Tab),
+            (Action::SaveReplay, KeyCode::F5),
+            (Action::PlayReplay, KeyCode::F6),
+            (Action::MenuUp, KeyCode::ArrowUp),
+            (Action::MenuDown, KeyCode::ArrowDown),
+            (Action::MenuConfirm, KeyCode::Enter),
+        ])
+    }
+}
+
+impl KeyBindings {
+    pub fn key(&self, action: Action) -> Option<KeyCode> {
+        self.0
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, key)| *key)
+    }
+
+    /// Binds `key` to `action`. An action already on `key` takes over the old key of
+    /// `action` instead, so no key ever ends up triggering two actions.
+    pub fn rebind(&mut self, action: Action, key: KeyCode) {
+        let previous = self.key(action);
+        if let Some(index) = self.0.iter().position(|(a, k)| *k == key && *a != action) {
+            match previous {
+                Some(previous) => self.0[index].1 = previous,
+                None => {
+                    self.0.remove(index);
+                }
+            }
+        }
+
+        match self.0.iter_mut().find(|(a, _)| *a == action) {
+            Some(binding) => binding.1 = key,
+            None => self.0.push((action, key)),
+        }
+    }
+}
+
+/// A short, human readable name for a key, e.g. `SPACE` or `D`.
+pub fn key_label(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_uppercase()
+}
+
+pub fn read_input(
+    buttons: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut action_input: ResMut<ActionInput>,
+    player: Res<ReplayPlayer>,
+) {
+    action_input.0.clear();
+
+    // Presses come from the replay instead, a fixed step at a time in `begin_step`.
+    if player.is_playing() {
+        return;
+    }
+
+    action_input.0.extend(
+        bindings
+            .0
+            .iter()
+            .map(|binding| ActionState::read(*binding, &buttons)),
+    );
+}
+
+/// Hands the presses latched since the last fixed step, or the replay's presses for
+/// this step, to the step. Replayed presses also show up in [`ActionInput`] for this frame.
+pub fn begin_step(
+    bindings: Res<KeyBindings>,
+    mut step_input: ResMut<StepInput>,
+    mut action_input: ResMut<ActionInput>,
+    mut player: ResMut<ReplayPlayer>,
+) {
+    let latched = std::mem::take(&mut step_input.latched);
+    if !player.is_playing() {
+        step_input.just_pressed = latched;
+        return;
+    }
 
-    let actions = mappings.map(|mapping| ActionState::read(mapping, &buttons));
-    action_input.0 = actions;
+    let bits = player.step();
+    step_input.just_pressed = bits;
+    for (action, _) in &bindings.0 {
+        if bits & action.bit() != 0 {
+            action_input.press(*action);
+        }
+    }
 }
 
 pub struct InputMappingBundle;
@@ -85,6 +252,10 @@ pub struct InputMappingBundle;
 impl Plugin for InputMappingBundle {
     fn build(&self, app: &mut App) {
         app.init_resource::<ActionInput>()
-            .add_systems(PreUpdate, read_input);
+            .init_resource::<ReplayPlayer>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<StepInput>()
+            .add_systems(PreUpdate, read_input)
+            .add_systems(FixedUpdate, begin_step);
     }
 }
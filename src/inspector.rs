@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::quick::WorldInspectorPlugin;
+
+use crate::game::{Grip, Progress, Radius, Speed};
+
+/// Live world inspector for tweaking the level while it runs.
+pub struct InspectorBundle;
+
+impl Plugin for InspectorBundle {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(WorldInspectorPlugin::new())
+            .register_type::<Progress>()
+            .register_type::<Speed>()
+            .register_type::<Radius>()
+            .register_type::<Grip>();
+    }
+}
@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+use crate::game::{Difficulty, GameMode};
+
+/// Options given when the game was launched, from the command line or the page URL.
+#[derive(Resource, Default)]
+pub struct LaunchOptions {
+    /// The level to start at instead of the first one, as in `--level 2` or `?level=2`.
+    pub level: Option<usize>,
+    /// The difficulty to start with, as in `--difficulty hard` or `?difficulty=hard`.
+    pub difficulty: Option<Difficulty>,
+    /// The seed for generated levels, as in `--seed 42` or `?seed=42`.
+    pub seed: Option<u64>,
+    /// The mode to play, as in `--mode endless` or `?mode=endless`.
+    pub mode: Option<GameMode>,
+}
+
+/// Where launch options are read from on this platform.
+trait ArgSource {
+    fn value(&self, name: &str) -> Option<String>;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct CommandLine(Vec<String>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ArgSource for CommandLine {
+    fn value(&self, name: &str) -> Option<String> {
+        let flag = format!("--{name}");
+        let index = self.0.iter().position(|arg| *arg == flag)?;
+        self.0.get(index + 1).cloned()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+struct UrlQuery(String);
+
+#[cfg(target_arch = "wasm32")]
+impl ArgSource for UrlQuery {
+    fn value(&self, name: &str) -> Option<String> {
+        self.0.trim_start_matches('?').split('&').find_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            (key == name).then(|| value.to_string())
+        })
+    }
+}
+
+fn platform_args() -> Box<dyn ArgSource> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return Box::new(CommandLine(std::env::args().collect()));
+    #[cfg(target_arch = "wasm32")]
+    return Box::new(UrlQuery(
+        web_sys::window()
+            .and_then(|window| window.location().search().ok())
+            .unwrap_or_default(),
+    ));
+}
+
+impl LaunchOptions {
+    fn from_args(args: &dyn ArgSource) -> Self {
+        let level = args.value("level").and_then(|value| match value.parse() {
+            Ok(level) => Some(level),
+            Err(_) => {
+                warn!("Ignoring invalid level {value:?}");
+                None
+            }
+        });
+        let difficulty =
+            args.value("difficulty")
+                .and_then(|value| match Difficulty::from_name(&value) {
+                    Some(difficulty) => Some(difficulty),
+                    None => {
+                        warn!("Ignoring invalid difficulty {value:?}");
+                        None
+                    }
+                });
+        let seed = args.value("seed").and_then(|value| match value.parse() {
+            Ok(seed) => Some(seed),
+            Err(_) => {
+                warn!("Ignoring invalid seed {value:?}");
+                None
+            }
+        });
+        let mode = args
+            .value("mode")
+            .and_then(|value| match GameMode::from_name(&value) {
+                Some(mode) => Some(mode),
+                None => {
+                    warn!("Ignoring invalid mode {value:?}");
+                    None
+                }
+            });
+        Self {
+            level,
+            difficulty,
+            seed,
+            mode,
+        }
+    }
+}
+
+pub struct LaunchBundle;
+
+impl Plugin for LaunchBundle {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LaunchOptions::from_args(platform_args().as_ref()));
+    }
+}
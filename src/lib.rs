@@ -0,0 +1,39 @@
+// Bevy code commonly triggers these lints and they may be important signals
+// about code quality. They are sometimes hard to avoid though, and the CI
+// workflow treats them as errors, so this allows them throughout the project.
+// Feel free to delete this line.
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+mod achievements;
+mod debug;
+mod game;
+mod input;
+#[cfg(feature = "inspector")]
+mod inspector;
+mod launch;
+mod menu;
+mod replay;
+mod scores;
+mod settings;
+mod toast;
+
+pub use achievements::AchievementsBundle;
+pub use debug::DebugBundle;
+pub use game::GameBundle;
+pub use input::InputMappingBundle;
+#[cfg(feature = "inspector")]
+pub use inspector::InspectorBundle;
+pub use launch::LaunchBundle;
+pub use menu::MenuBundle;
+pub use replay::ReplayBundle;
+pub use scores::ScoresBundle;
+pub use settings::SettingsBundle;
+pub use toast::ToastBundle;
+
+/// Types plugins can rely on to react to play. Anything else in `game` may change between versions.
+///
+/// [`Grip`] took over from the old `Holding` component: a hand holding the baton has
+/// `Grip::Holding(baton)` instead.
+pub use game::{
+    Active, Cycle, Finish, GameEvent, Grip, Hand, Item, LevelCompleted, Overlap, Quality,
+};
@@ -1,18 +1,51 @@
-// Bevy code commonly triggers these lints and they may be important signals
-// about code quality. They are sometimes hard to avoid though, and the CI
-// workflow treats them as errors, so this allows them throughout the project.
-// Feel free to delete this line.
-#![allow(clippy::too_many_arguments, clippy::type_complexity)]
-
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::asset::io::file::FileAssetReader;
 use bevy::asset::AssetMetaCheck;
 use bevy::prelude::*;
-use debug::DebugBundle;
-use game::GameBundle;
-use input::InputMappingBundle;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::winit::WinitWindows;
+#[cfg(feature = "inspector")]
+use bevy_github_ci_template::InspectorBundle;
+use bevy_github_ci_template::{
+    AchievementsBundle, DebugBundle, GameBundle, InputMappingBundle, LaunchBundle, MenuBundle,
+    ReplayBundle, ScoresBundle, SettingsBundle, ToastBundle,
+};
+
+const WINDOW_TITLE: &str = "Hand Relay";
+/// Image in the assets folder used as the window icon on native builds.
+#[cfg(not(target_arch = "wasm32"))]
+const WINDOW_ICON: &str = "hand-open.png";
+
+/// Browsers show the page's favicon instead, so this only exists on native builds.
+#[cfg(not(target_arch = "wasm32"))]
+fn system_set_window_icon(windows: NonSend<WinitWindows>) {
+    let path = FileAssetReader::get_base_path()
+        .join("assets")
+        .join(WINDOW_ICON);
+    let image = match image::open(&path) {
+        Ok(image) => image.into_rgba8(),
+        Err(error) => {
+            warn!("Could not load window icon {}: {error}", path.display());
+            return;
+        }
+    };
 
-mod debug;
-mod game;
-mod input;
+    let (width, height) = image.dimensions();
+    let icon = match winit::window::Icon::from_rgba(image.into_raw(), width, height) {
+        Ok(icon) => icon,
+        Err(error) => {
+            warn!(
+                "Could not use {} as the window icon: {error}",
+                path.display()
+            );
+            return;
+        }
+    };
+
+    for window in windows.windows.values() {
+        window.set_window_icon(Some(icon.clone()));
+    }
+}
 
 fn main() {
     let mut app = App::new();
@@ -21,14 +54,39 @@ fn main() {
         app.add_plugins(DebugBundle);
     }
 
-    app.add_plugins(DefaultPlugins.set(AssetPlugin {
-        // Wasm builds will check for meta files (that don't exist) if this isn't set.
-        // This causes errors and even panics in web builds on itch.
-        // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
-        meta_check: AssetMetaCheck::Never,
-        ..default()
-    }))
+    app.add_plugins(
+        DefaultPlugins
+            .set(AssetPlugin {
+                // Wasm builds will check for meta files (that don't exist) if this isn't set.
+                // This causes errors and even panics in web builds on itch.
+                // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
+                meta_check: AssetMetaCheck::Never,
+                ..default()
+            })
+            .set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: WINDOW_TITLE.to_string(),
+                    ..default()
+                }),
+                ..default()
+            }),
+    )
+    .add_plugins(LaunchBundle)
     .add_plugins(InputMappingBundle)
     .add_plugins(GameBundle)
-    .run();
+    .add_plugins(SettingsBundle)
+    .add_plugins(MenuBundle)
+    .add_plugins(ReplayBundle)
+    .add_plugins(ScoresBundle)
+    .add_plugins(AchievementsBundle)
+    .add_plugins(ToastBundle);
+
+    // Needs the window and input from `DefaultPlugins`, so it goes in last.
+    #[cfg(feature = "inspector")]
+    app.add_plugins(InspectorBundle);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    app.add_systems(Startup, system_set_window_icon);
+
+    app.run();
 }
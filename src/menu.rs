@@ -0,0 +1,234 @@
+use bevy::prelude::*;
+
+use crate::game::{AppState, Game, RestartLevel};
+use crate::input::{Action, ActionInput};
+
+const BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.15);
+const BUTTON_SELECTED_COLOR: Color = Color::srgb(0.3, 0.3, 0.3);
+
+#[derive(Clone, Copy)]
+enum MenuAction {
+    Play,
+    Settings,
+    Exit,
+    Resume,
+    Restart,
+    QuitToMenu,
+}
+
+const MAIN_ACTIONS: [(MenuAction, &str); 3] = [
+    (MenuAction::Play, "PLAY"),
+    (MenuAction::Settings, "SETTINGS"),
+    (MenuAction::Exit, "QUIT"),
+];
+
+const PAUSE_ACTIONS: [(MenuAction, &str); 3] = [
+    (MenuAction::Resume, "RESUME"),
+    (MenuAction::Restart, "RESTART"),
+    (MenuAction::QuitToMenu, "QUIT TO MENU"),
+];
+
+#[derive(Component)]
+struct MenuButton {
+    action: MenuAction,
+    index: usize,
+}
+
+#[derive(Component)]
+struct PauseMenu;
+
+/// The button picked by keyboard navigation, out of how many the open menu has.
+#[derive(Resource, Default)]
+struct MenuSelection {
+    index: usize,
+    count: usize,
+}
+
+fn spawn_button(parent: &mut ChildBuilder, action: MenuAction, index: usize, label: &str) {
+    parent
+        .spawn((
+            MenuButton { action, index },
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(240.),
+                    padding: UiRect::axes(Val::Px(16.), Val::Px(8.)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: BUTTON_COLOR.into(),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font_size: 32.,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn spawn_menu(
+    commands: &mut Commands,
+    selection: &mut MenuSelection,
+    title: &str,
+    actions: &[(MenuAction, &str)],
+    background: Color,
+) -> Entity {
+    *selection = MenuSelection {
+        index: 0,
+        count: actions.len(),
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(16.),
+                ..default()
+            },
+            background_color: background.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                title,
+                TextStyle {
+                    font_size: 64.,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            for (index, (action, label)) in actions.iter().enumerate() {
+                spawn_button(parent, *action, index, label);
+            }
+        })
+        .id()
+}
+
+fn system_show_main_menu(mut commands: Commands, mut selection: ResMut<MenuSelection>) {
+    let menu = spawn_menu(
+        &mut commands,
+        &mut selection,
+        "HAND RELAY",
+        &MAIN_ACTIONS,
+        Color::BLACK,
+    );
+    commands.entity(menu).insert(StateScoped(AppState::Menu));
+}
+
+fn system_show_pause_menu(mut commands: Commands, mut selection: ResMut<MenuSelection>) {
+    let menu = spawn_menu(
+        &mut commands,
+        &mut selection,
+        "PAUSED",
+        &PAUSE_ACTIONS,
+        Color::srgba(0., 0., 0., 0.5),
+    );
+    commands.entity(menu).insert(PauseMenu);
+}
+
+fn system_hide_pause_menu(mut commands: Commands, query: Query<Entity, With<PauseMenu>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn system_navigate_menu(action_input: Res<ActionInput>, mut selection: ResMut<MenuSelection>) {
+    if selection.count == 0 {
+        return;
+    }
+
+    if action_input.just_pressed(Action::MenuUp) {
+        selection.index = (selection.index + selection.count - 1) % selection.count;
+    }
+    if action_input.just_pressed(Action::MenuDown) {
+        selection.index = (selection.index + 1) % selection.count;
+    }
+}
+
+fn system_menu_buttons(
+    action_input: Res<ActionInput>,
+    mut selection: ResMut<MenuSelection>,
+    interactions: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    buttons: Query<&MenuButton>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_state: ResMut<NextState<Game>>,
+    mut restart: EventWriter<RestartLevel>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let mut chosen = None;
+    for (interaction, button) in &interactions {
+        match interaction {
+            Interaction::Pressed => chosen = Some(button.action),
+            Interaction::Hovered => selection.index = button.index,
+            Interaction::None => {}
+        }
+    }
+
+    if action_input.just_pressed(Action::MenuConfirm) {
+        chosen = buttons
+            .iter()
+            .find(|button| button.index == selection.index)
+            .map(|button| button.action);
+    }
+
+    match chosen {
+        Some(MenuAction::Play) => next_app_state.set(AppState::InGame),
+        Some(MenuAction::Settings) => next_app_state.set(AppState::Settings),
+        Some(MenuAction::Exit) => {
+            exit.send(AppExit::Success);
+        }
+        Some(MenuAction::Resume) => next_state.set(Game::Playing),
+        Some(MenuAction::Restart) => {
+            restart.send(RestartLevel);
+        }
+        Some(MenuAction::QuitToMenu) => next_app_state.set(AppState::Menu),
+        None => {}
+    }
+}
+
+fn system_highlight_selection(
+    selection: Res<MenuSelection>,
+    mut buttons: Query<(&MenuButton, &mut BackgroundColor)>,
+) {
+    for (button, mut background) in &mut buttons {
+        let color = if button.index == selection.index {
+            BUTTON_SELECTED_COLOR
+        } else {
+            BUTTON_COLOR
+        };
+        background.set_if_neq(color.into());
+    }
+}
+
+pub struct MenuBundle;
+
+impl Plugin for MenuBundle {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuSelection>()
+            .add_systems(OnEnter(AppState::Menu), system_show_main_menu)
+            .add_systems(OnEnter(Game::Paused), system_show_pause_menu)
+            .add_systems(OnExit(Game::Paused), system_hide_pause_menu)
+            .add_systems(
+                Update,
+                (
+                    system_navigate_menu,
+                    system_menu_buttons,
+                    system_highlight_selection,
+                )
+                    .chain()
+                    .run_if(in_state(Game::Paused).or_else(in_state(AppState::Menu))),
+            );
+    }
+}
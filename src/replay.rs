@@ -0,0 +1,253 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    AppState, CurrentLevel, Difficulty, Game, LevelSeed, Levels, RestartLevel, RunDifficulty,
+};
+use crate::input::{self, Action, ActionInput, StepInput};
+
+#[cfg(not(target_arch = "wasm32"))]
+const REPLAY_PATH: &str = "replay.ron";
+
+/// Bumped whenever a change would make older replays play back differently.
+const REPLAY_VERSION: u32 = 3;
+
+/// One fixed step of a recorded run.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReplayFrame {
+    /// Packed [`Action::bit`]s of the actions pressed for this step.
+    pub just_pressed: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Replay {
+    /// The [`REPLAY_VERSION`] this replay was recorded with. Replays from before
+    /// versioning read as 0.
+    #[serde(default)]
+    pub version: u32,
+    pub seed: u64,
+    pub level: usize,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Replay, ron::error::SpannedError> {
+        ron::de::from_bytes(bytes)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).map(String::into_bytes)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ReplayRecorder {
+    pub recording: bool,
+    pub replay: Replay,
+}
+
+/// The most recently saved replay, serialized. On the web this is the only copy.
+#[derive(Resource, Default)]
+pub struct SavedReplay(pub Vec<u8>);
+
+/// Feeds recorded frames back into [`ActionInput`] in place of the keyboard.
+#[derive(Resource, Default)]
+pub struct ReplayPlayer {
+    /// Replay waiting for the restarted level's countdown to begin.
+    queued: Option<Replay>,
+    frames: Vec<ReplayFrame>,
+    cursor: usize,
+}
+
+impl ReplayPlayer {
+    pub fn is_playing(&self) -> bool {
+        self.cursor < self.frames.len()
+    }
+
+    /// Advances playback by one fixed step and returns the actions pressed for it.
+    pub fn step(&mut self) -> u32 {
+        let bits = self
+            .frames
+            .get(self.cursor)
+            .map_or(0, |frame| frame.just_pressed);
+        self.cursor += 1;
+        bits
+    }
+
+    fn start(&mut self, replay: Replay) {
+        self.frames = replay.frames;
+        self.cursor = 0;
+    }
+}
+
+fn load_replay(saved: &SavedReplay) -> Option<Replay> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let bytes = match std::fs::read(REPLAY_PATH) {
+        Ok(bytes) => bytes,
+        Err(_) => saved.0.clone(),
+    };
+    #[cfg(target_arch = "wasm32")]
+    let bytes = saved.0.clone();
+
+    if bytes.is_empty() {
+        return None;
+    }
+
+    match Replay::from_bytes(&bytes) {
+        Ok(replay) if replay.version != REPLAY_VERSION => {
+            warn!(
+                "Ignoring replay from version {}, expected {REPLAY_VERSION}",
+                replay.version
+            );
+            None
+        }
+        Ok(replay) => Some(replay),
+        Err(error) => {
+            warn!("Could not read replay: {error}");
+            None
+        }
+    }
+}
+
+fn system_play_replay_on_action(
+    action_input: Res<ActionInput>,
+    saved: Res<SavedReplay>,
+    levels: Res<Levels>,
+    mut player: ResMut<ReplayPlayer>,
+    mut seed: ResMut<LevelSeed>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut difficulty: ResMut<Difficulty>,
+    mut restart: EventWriter<RestartLevel>,
+) {
+    if !action_input.just_pressed(Action::PlayReplay) || player.is_playing() {
+        return;
+    }
+
+    let Some(replay) = load_replay(&saved) else {
+        return;
+    };
+
+    if replay.level >= levels.count() {
+        warn!(
+            "Ignoring replay of level {}, which doesn't exist",
+            replay.level
+        );
+        return;
+    }
+
+    seed.0 = replay.seed;
+    current_level.0 = replay.level;
+    *difficulty = replay.difficulty;
+    player.queued = Some(replay);
+    restart.send(RestartLevel);
+}
+
+fn system_start_playback(mut player: ResMut<ReplayPlayer>) {
+    if let Some(replay) = player.queued.take() {
+        player.start(replay);
+    }
+}
+
+fn system_start_recording(
+    mut recorder: ResMut<ReplayRecorder>,
+    player: Res<ReplayPlayer>,
+    seed: Res<LevelSeed>,
+    current_level: Res<CurrentLevel>,
+    difficulty: Res<RunDifficulty>,
+) {
+    // Don't overwrite the replay that is about to be watched.
+    recorder.recording = player.queued.is_none();
+    recorder.replay = Replay {
+        version: REPLAY_VERSION,
+        seed: seed.0,
+        level: current_level.0,
+        difficulty: difficulty.0,
+        frames: vec![],
+    };
+}
+
+fn system_record_step(mut recorder: ResMut<ReplayRecorder>, step_input: Res<StepInput>) {
+    if !recorder.recording {
+        return;
+    }
+
+    // Played back, these would save over or restart the replay being watched.
+    let replay_bits = Action::SaveReplay.bit() | Action::PlayReplay.bit();
+    recorder.replay.frames.push(ReplayFrame {
+        just_pressed: step_input.just_pressed & !replay_bits,
+    });
+}
+
+fn save_replay(recorder: &ReplayRecorder, saved: &mut SavedReplay) {
+    let bytes = match recorder.replay.to_bytes() {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            warn!("Could not serialize replay: {error}");
+            return;
+        }
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        if let Err(error) = std::fs::write(REPLAY_PATH, &bytes) {
+            warn!("Could not write replay to {REPLAY_PATH}: {error}");
+        }
+    }
+
+    saved.0 = bytes;
+}
+
+fn system_save_replay_on_action(
+    action_input: Res<ActionInput>,
+    recorder: Res<ReplayRecorder>,
+    player: Res<ReplayPlayer>,
+    mut saved: ResMut<SavedReplay>,
+) {
+    // The run being watched isn't recorded, so there is nothing new to save.
+    if action_input.just_pressed(Action::SaveReplay) && !player.is_playing() {
+        save_replay(&recorder, &mut saved);
+    }
+}
+
+fn system_finish_recording(mut recorder: ResMut<ReplayRecorder>, mut saved: ResMut<SavedReplay>) {
+    if !recorder.recording {
+        return;
+    }
+
+    recorder.recording = false;
+    save_replay(&recorder, &mut saved);
+}
+
+pub struct ReplayBundle;
+
+impl Plugin for ReplayBundle {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayRecorder>()
+            .init_resource::<SavedReplay>()
+            .add_systems(
+                OnEnter(Game::Countdown),
+                (system_start_recording, system_start_playback).chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    // The level would be swapped out from under loading or the tutorial.
+                    system_play_replay_on_action
+                        .run_if(not(in_state(Game::Loading)))
+                        .run_if(not(in_state(Game::Tutorial))),
+                    system_save_replay_on_action,
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                FixedUpdate,
+                system_record_step
+                    .after(input::begin_step)
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(OnEnter(Game::Finished), system_finish_recording)
+            .add_systems(OnEnter(Game::Failed), system_finish_recording);
+    }
+}
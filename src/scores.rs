@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::game::Difficulty;
+
+const SCORES_NAME: &str = "highscores";
+
+/// Somewhere to keep serialized progress between sessions.
+pub trait SaveStorage: Send + Sync {
+    fn load(&self) -> Option<String>;
+    fn save(&self, contents: &str);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct FileStorage(String);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SaveStorage for FileStorage {
+    fn load(&self) -> Option<String> {
+        std::fs::read_to_string(&self.0).ok()
+    }
+
+    fn save(&self, contents: &str) {
+        if let Err(error) = std::fs::write(&self.0, contents) {
+            warn!("Could not write {}: {error}", self.0);
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+struct LocalStorage(String);
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl SaveStorage for LocalStorage {
+    fn load(&self) -> Option<String> {
+        Self::storage()?.get_item(&self.0).ok()?
+    }
+
+    fn save(&self, contents: &str) {
+        let saved = Self::storage().map(|storage| storage.set_item(&self.0, contents));
+        if !matches!(saved, Some(Ok(()))) {
+            warn!("Could not write {} to local storage", self.0);
+        }
+    }
+}
+
+/// Storage for `name`, as `name.ron` on native and a `hand-relay-name` key in local storage on the web.
+pub fn platform_storage(name: &str) -> Box<dyn SaveStorage> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return Box::new(FileStorage(format!("{name}.ron")));
+    #[cfg(target_arch = "wasm32")]
+    return Box::new(LocalStorage(format!("hand-relay-{name}")));
+}
+
+/// Best finishing time per level index and difficulty.
+#[derive(Resource)]
+pub struct HighScores {
+    best: HashMap<(usize, Difficulty), Duration>,
+    storage: Box<dyn SaveStorage>,
+}
+
+impl Default for HighScores {
+    fn default() -> Self {
+        let storage = platform_storage(SCORES_NAME);
+        let best = storage
+            .load()
+            .and_then(|contents| match ron::from_str(&contents) {
+                Ok(best) => Some(best),
+                Err(error) => {
+                    warn!("Could not read high scores: {error}");
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self { best, storage }
+    }
+}
+
+impl HighScores {
+    pub fn best(&self, level: usize, difficulty: Difficulty) -> Option<Duration> {
+        self.best.get(&(level, difficulty)).copied()
+    }
+
+    /// Records `time` for `level` on `difficulty` and saves it if it beats the best,
+    /// returning whether it did.
+    pub fn submit(&mut self, level: usize, difficulty: Difficulty, time: Duration) -> bool {
+        if self
+            .best(level, difficulty)
+            .is_some_and(|best| best <= time)
+        {
+            return false;
+        }
+
+        self.best.insert((level, difficulty), time);
+        match ron::to_string(&self.best) {
+            Ok(contents) => self.storage.save(&contents),
+            Err(error) => warn!("Could not serialize high scores: {error}"),
+        }
+
+        true
+    }
+}
+
+pub struct ScoresBundle;
+
+impl Plugin for ScoresBundle {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HighScores>();
+    }
+}
@@ -0,0 +1,386 @@
+use bevy::prelude::*;
+
+use crate::game::{AppState, AutoGrab, ColorScheme, Difficulty, Game, Volume};
+use crate::input::{key_label, Action, ActionInput, KeyBindings};
+
+const VOLUME_STEP: f32 = 0.1;
+const BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.15);
+const BUTTON_HOVER_COLOR: Color = Color::srgb(0.3, 0.3, 0.3);
+
+#[derive(Clone, Copy)]
+enum VolumeChannel {
+    Master,
+    Sfx,
+    Music,
+}
+
+impl VolumeChannel {
+    fn label(self) -> &'static str {
+        match self {
+            VolumeChannel::Master => "MASTER",
+            VolumeChannel::Sfx => "SFX",
+            VolumeChannel::Music => "MUSIC",
+        }
+    }
+
+    fn get(self, volume: &Volume) -> f32 {
+        match self {
+            VolumeChannel::Master => volume.master,
+            VolumeChannel::Sfx => volume.sfx,
+            VolumeChannel::Music => volume.music,
+        }
+    }
+
+    fn get_mut(self, volume: &mut Volume) -> &mut f32 {
+        match self {
+            VolumeChannel::Master => &mut volume.master,
+            VolumeChannel::Sfx => &mut volume.sfx,
+            VolumeChannel::Music => &mut volume.music,
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+enum SettingsButton {
+    Lower(VolumeChannel),
+    Raise(VolumeChannel),
+    Rebind(Action),
+    ToggleAutoGrab,
+    NextColorScheme,
+    NextDifficulty,
+    Back,
+}
+
+#[derive(Component)]
+struct SettingsMenu;
+
+#[derive(Component)]
+struct VolumeText(VolumeChannel);
+
+#[derive(Component)]
+struct BindingText(Action);
+
+#[derive(Component)]
+struct AutoGrabText;
+
+#[derive(Component)]
+struct ColorSchemeText;
+
+#[derive(Component)]
+struct DifficultyText;
+
+/// The state to go back to when the settings menu closes.
+#[derive(Resource)]
+struct SettingsReturn(Game);
+
+impl Default for SettingsReturn {
+    fn default() -> Self {
+        Self(Game::Playing)
+    }
+}
+
+/// The action waiting for its next key press, if any.
+#[derive(Resource, Default)]
+struct Rebinding(Option<Action>);
+
+fn text(value: impl Into<String>, font_size: f32) -> TextBundle {
+    TextBundle {
+        text: Text::from_section(
+            value,
+            TextStyle {
+                font_size,
+                color: Color::WHITE,
+                ..default()
+            },
+        ),
+        ..default()
+    }
+}
+
+fn row() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            display: Display::Flex,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(16.),
+            ..default()
+        },
+        ..default()
+    }
+}
+
+fn spawn_button(parent: &mut ChildBuilder, button: SettingsButton, label: &str) {
+    parent
+        .spawn((
+            button,
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::axes(Val::Px(16.), Val::Px(8.)),
+                    ..default()
+                },
+                background_color: BUTTON_COLOR.into(),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(text(label, 32.));
+        });
+}
+
+fn system_toggle_settings(
+    action_input: Res<ActionInput>,
+    rebinding: Res<Rebinding>,
+    state: Res<State<Game>>,
+    mut next_state: ResMut<NextState<Game>>,
+    mut settings_return: ResMut<SettingsReturn>,
+) {
+    if !action_input.just_pressed(Action::Settings) || rebinding.0.is_some() {
+        return;
+    }
+
+    match state.get() {
+        Game::Playing | Game::Paused => {
+            settings_return.0 = state.get().clone();
+            next_state.set(Game::Settings);
+        }
+        Game::Settings => next_state.set(settings_return.0.clone()),
+        _ => {}
+    }
+}
+
+fn system_show_settings(mut commands: Commands) {
+    commands
+        .spawn((
+            SettingsMenu,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(16.),
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.8).into(),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(text("SETTINGS", 64.));
+
+            for channel in [
+                VolumeChannel::Master,
+                VolumeChannel::Sfx,
+                VolumeChannel::Music,
+            ] {
+                parent.spawn(row()).with_children(|parent| {
+                    parent.spawn(text(channel.label(), 32.));
+                    spawn_button(parent, SettingsButton::Lower(channel), "-");
+                    parent.spawn((VolumeText(channel), text("", 32.)));
+                    spawn_button(parent, SettingsButton::Raise(channel), "+");
+                });
+            }
+
+            parent.spawn(row()).with_children(|parent| {
+                parent.spawn(text("GRAB", 32.));
+                parent.spawn((BindingText(Action::Grab), text("", 32.)));
+                spawn_button(parent, SettingsButton::Rebind(Action::Grab), "REBIND");
+            });
+
+            parent.spawn(row()).with_children(|parent| {
+                parent.spawn(text("AUTO GRAB", 32.));
+                parent.spawn((AutoGrabText, text("", 32.)));
+                spawn_button(parent, SettingsButton::ToggleAutoGrab, "TOGGLE");
+            });
+
+            parent.spawn(row()).with_children(|parent| {
+                parent.spawn(text("COLORS", 32.));
+                parent.spawn((ColorSchemeText, text("", 32.)));
+                spawn_button(parent, SettingsButton::NextColorScheme, "CHANGE");
+            });
+
+            parent.spawn(row()).with_children(|parent| {
+                parent.spawn(text("DIFFICULTY", 32.));
+                parent.spawn((DifficultyText, text("", 32.)));
+                spawn_button(parent, SettingsButton::NextDifficulty, "CHANGE");
+            });
+
+            spawn_button(parent, SettingsButton::Back, "BACK");
+        });
+}
+
+fn system_hide_settings(
+    mut commands: Commands,
+    mut rebinding: ResMut<Rebinding>,
+    query: Query<Entity, With<SettingsMenu>>,
+) {
+    rebinding.0 = None;
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn system_settings_buttons(
+    mut interactions: Query<
+        (&Interaction, &SettingsButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut volume: ResMut<Volume>,
+    mut rebinding: ResMut<Rebinding>,
+    mut auto_grab: ResMut<AutoGrab>,
+    mut scheme: ResMut<ColorScheme>,
+    mut difficulty: ResMut<Difficulty>,
+    settings_return: Res<SettingsReturn>,
+    app_state: Res<State<AppState>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_state: ResMut<NextState<Game>>,
+) {
+    for (interaction, button, mut background) in &mut interactions {
+        match interaction {
+            Interaction::Pressed => match button {
+                SettingsButton::Lower(channel) => {
+                    let value = channel.get_mut(&mut volume);
+                    *value = (*value - VOLUME_STEP).clamp(0., 1.);
+                }
+                SettingsButton::Raise(channel) => {
+                    let value = channel.get_mut(&mut volume);
+                    *value = (*value + VOLUME_STEP).clamp(0., 1.);
+                }
+                SettingsButton::Rebind(action) => rebinding.0 = Some(*action),
+                SettingsButton::ToggleAutoGrab => auto_grab.0 = !auto_grab.0,
+                SettingsButton::NextColorScheme => *scheme = scheme.next(),
+                SettingsButton::NextDifficulty => *difficulty = difficulty.next(),
+                SettingsButton::Back => match app_state.get() {
+                    AppState::Settings => next_app_state.set(AppState::Menu),
+                    _ => next_state.set(settings_return.0.clone()),
+                },
+            },
+            Interaction::Hovered => *background = BUTTON_HOVER_COLOR.into(),
+            Interaction::None => *background = BUTTON_COLOR.into(),
+        }
+    }
+}
+
+fn system_capture_rebind(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<KeyBindings>,
+) {
+    let Some(action) = rebinding.0 else {
+        return;
+    };
+
+    if let Some(key) = keys.get_just_pressed().next() {
+        bindings.rebind(action, *key);
+        rebinding.0 = None;
+    }
+}
+
+fn system_update_settings_text(
+    volume: Res<Volume>,
+    bindings: Res<KeyBindings>,
+    rebinding: Res<Rebinding>,
+    auto_grab: Res<AutoGrab>,
+    scheme: Res<ColorScheme>,
+    difficulty: Res<Difficulty>,
+    mut volume_texts: Query<(&mut Text, &VolumeText), Without<BindingText>>,
+    mut binding_texts: Query<(&mut Text, &BindingText), Without<VolumeText>>,
+    mut auto_grab_texts: Query<
+        &mut Text,
+        (
+            With<AutoGrabText>,
+            Without<VolumeText>,
+            Without<BindingText>,
+        ),
+    >,
+    mut scheme_texts: Query<
+        &mut Text,
+        (
+            With<ColorSchemeText>,
+            Without<VolumeText>,
+            Without<BindingText>,
+            Without<AutoGrabText>,
+        ),
+    >,
+    mut difficulty_texts: Query<
+        &mut Text,
+        (
+            With<DifficultyText>,
+            Without<VolumeText>,
+            Without<BindingText>,
+            Without<AutoGrabText>,
+            Without<ColorSchemeText>,
+        ),
+    >,
+) {
+    for (mut text, VolumeText(channel)) in &mut volume_texts {
+        let value = format!("{:.0}%", channel.get(&volume) * 100.);
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+
+    for (mut text, BindingText(action)) in &mut binding_texts {
+        let value = if rebinding.0 == Some(*action) {
+            "PRESS A KEY".to_string()
+        } else {
+            bindings.key(*action).map(key_label).unwrap_or_default()
+        };
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+
+    let value = if auto_grab.0 { "ON" } else { "OFF" };
+    for mut text in &mut auto_grab_texts {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.to_string();
+        }
+    }
+
+    let value = scheme.label();
+    for mut text in &mut scheme_texts {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.to_string();
+        }
+    }
+
+    let value = difficulty.label();
+    for mut text in &mut difficulty_texts {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.to_string();
+        }
+    }
+}
+
+pub struct SettingsBundle;
+
+impl Plugin for SettingsBundle {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsReturn>()
+            .init_resource::<Rebinding>()
+            .add_systems(
+                Update,
+                // Sees the rebind still waiting, so the captured key doesn't also close the panel.
+                system_toggle_settings
+                    .before(system_capture_rebind)
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(OnEnter(Game::Settings), system_show_settings)
+            .add_systems(OnExit(Game::Settings), system_hide_settings)
+            .add_systems(OnEnter(AppState::Settings), system_show_settings)
+            .add_systems(OnExit(AppState::Settings), system_hide_settings)
+            .add_systems(
+                Update,
+                (
+                    system_settings_buttons,
+                    system_capture_rebind,
+                    system_update_settings_text,
+                )
+                    .run_if(in_state(Game::Settings).or_else(in_state(AppState::Settings))),
+            );
+    }
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+/// Seconds a toast stays on screen, including sliding in and fading out.
+const TOAST_DURATION: f32 = 3.;
+const TOAST_SLIDE: f32 = 0.3;
+const TOAST_FADE: f32 = 0.5;
+const TOAST_WIDTH: f32 = 320.;
+/// Distance from the top of the screen to the first toast, clear of the timer HUD.
+const TOAST_TOP: f32 = 104.;
+const TOAST_SPACING: f32 = 56.;
+const TOAST_MARGIN: f32 = 16.;
+const TOAST_BACKGROUND_ALPHA: f32 = 0.7;
+
+/// Asks for a short notification to be shown, stacked under any already showing.
+#[derive(Event)]
+pub struct ShowToast(pub String);
+
+#[derive(Component)]
+pub struct Toast {
+    pub text: String,
+    pub timer: Timer,
+}
+
+fn system_spawn_toasts(mut commands: Commands, mut events: EventReader<ShowToast>) {
+    for ShowToast(text) in events.read() {
+        let toast = Toast {
+            text: text.clone(),
+            timer: Timer::from_seconds(TOAST_DURATION, TimerMode::Once),
+        };
+        let label = TextBundle::from_section(
+            toast.text.clone(),
+            TextStyle {
+                font_size: 24.,
+                color: Color::WHITE,
+                ..default()
+            },
+        );
+
+        commands
+            .spawn((
+                toast,
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(TOAST_TOP),
+                        right: Val::Px(-TOAST_WIDTH),
+                        width: Val::Px(TOAST_WIDTH),
+                        padding: UiRect::axes(Val::Px(16.), Val::Px(8.)),
+                        ..default()
+                    },
+                    background_color: Color::srgba(0., 0., 0., TOAST_BACKGROUND_ALPHA).into(),
+                    ..default()
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn(label);
+            });
+    }
+}
+
+fn system_animate_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toasts: Query<(
+        Entity,
+        &mut Toast,
+        &mut Style,
+        &mut BackgroundColor,
+        &Children,
+    )>,
+    mut texts: Query<&mut Text>,
+) {
+    let mut order: Vec<(Entity, Duration)> = Vec::new();
+    for (entity, mut toast, ..) in &mut toasts {
+        if toast.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            order.push((entity, toast.timer.elapsed()));
+        }
+    }
+
+    // Oldest toasts stay on top and newer ones stack below them.
+    order.sort_by_key(|(_, elapsed)| std::cmp::Reverse(*elapsed));
+
+    for (index, (entity, _)) in order.into_iter().enumerate() {
+        let Ok((_, toast, mut style, mut background, children)) = toasts.get_mut(entity) else {
+            continue;
+        };
+
+        let slide = (toast.timer.elapsed_secs() / TOAST_SLIDE).min(1.);
+        let eased = 1. - (1. - slide).powi(2);
+        let alpha = (toast.timer.remaining_secs() / TOAST_FADE).min(1.);
+
+        style.right = Val::Px(-TOAST_WIDTH + (TOAST_WIDTH + TOAST_MARGIN) * eased);
+        style.top = Val::Px(TOAST_TOP + index as f32 * TOAST_SPACING);
+        background.0.set_alpha(TOAST_BACKGROUND_ALPHA * alpha);
+
+        for child in children {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                for section in &mut text.sections {
+                    section.style.color.set_alpha(alpha);
+                }
+            }
+        }
+    }
+}
+
+pub struct ToastBundle;
+
+impl Plugin for ToastBundle {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ShowToast>()
+            .add_systems(Update, (system_spawn_toasts, system_animate_toasts).chain());
+    }
+}